@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "\
+Usage: kappa [OPTIONS] [+LINE] [FILE[:LINE[:COL]]]...
+
+Arguments:
+  FILE[:LINE[:COL]]  Open FILE, optionally at LINE and COL (1-based)
+  +LINE              Open the next FILE at LINE
+  -                  Read the document from standard input
+
+With no FILE and piped input, kappa pages standard input as it arrives.
+Settings are read from ~/.config/kappa/config.toml, overridden by the
+nearest .kappa.toml in the working directory or its parents. ui.theme
+names a bundled theme (dark, light, high-contrast) or a file in
+~/.config/kappa/themes/. Colours are reduced to what the terminal
+supports; ui.colors (truecolor, 256, 16, none) overrides the detection.
+
+Options:
+  -R, --readonly     Open files read-only
+  -S, --session      Restore the session saved for this directory
+  -r, --recent       Start with the recent files list
+  -h, --help         Print this help and exit
+  -V, --version      Print the version and exit";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileArg {
+    /// `None` reads the document from stdin.
+    pub path: Option<PathBuf>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliOptions {
+    pub files: Vec<FileArg>,
+    pub read_only: bool,
+    pub session: bool,
+    pub recent: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliCommand {
+    Run(CliOptions),
+    Help,
+    Version,
+}
+
+impl CliOptions {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<CliCommand, String> {
+        let mut options = CliOptions::default();
+        let mut pending_line = None;
+        let mut only_files = false;
+
+        for arg in args {
+            if only_files {
+                options.files.push(FileArg::from_path(&arg, pending_line.take()));
+                continue;
+            }
+            match arg.as_str() {
+                "--" => only_files = true,
+                "-h" | "--help" => return Ok(CliCommand::Help),
+                "-V" | "--version" => return Ok(CliCommand::Version),
+                "-R" | "--readonly" => options.read_only = true,
+                "-S" | "--session" => options.session = true,
+                "-r" | "--recent" => options.recent = true,
+                "-" => {
+                    if options.files.iter().any(|f| f.path.is_none()) {
+                        return Err("standard input can only be read once".to_string());
+                    }
+                    options.files.push(FileArg {
+                        path: None,
+                        line: pending_line.take(),
+                        column: None,
+                    });
+                }
+                _ if arg.starts_with('+') => {
+                    let line = arg[1..]
+                        .parse::<usize>()
+                        .map_err(|_| format!("invalid line number '{}'", arg))?;
+                    pending_line = Some(line);
+                }
+                _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+                _ => options.files.push(FileArg::from_path(&arg, pending_line.take())),
+            }
+        }
+
+        if options.session && !options.files.is_empty() {
+            return Err("'--session' cannot be combined with files".to_string());
+        }
+        if options.recent && (options.session || !options.files.is_empty()) {
+            return Err("'--recent' cannot be combined with files or '--session'".to_string());
+        }
+        if pending_line.is_some() {
+            return Err("'+LINE' must be followed by a file".to_string());
+        }
+        Ok(CliCommand::Run(options))
+    }
+
+    pub fn reads_stdin(&self) -> bool {
+        self.files.iter().any(|f| f.path.is_none())
+    }
+}
+
+impl FileArg {
+    pub fn position(&self) -> Option<(usize, usize)> {
+        if self.line.is_none() && self.column.is_none() {
+            return None;
+        }
+        let line = self.line.unwrap_or(1).saturating_sub(1);
+        let column = self.column.unwrap_or(1).saturating_sub(1);
+        Some((line, column))
+    }
+
+    /// Splits a trailing `:LINE` or `:LINE:COL` off `arg`, unless a file with
+    /// the literal name exists.
+    fn from_path(arg: &str, line: Option<usize>) -> Self {
+        if !Path::new(arg).exists()
+            && let Some((path, parsed_line, column)) = split_position(arg)
+        {
+            return Self {
+                path: Some(PathBuf::from(path)),
+                line: Some(parsed_line),
+                column,
+            };
+        }
+        Self {
+            path: Some(PathBuf::from(arg)),
+            line,
+            column: None,
+        }
+    }
+}
+
+fn split_position(arg: &str) -> Option<(&str, usize, Option<usize>)> {
+    let (rest, last) = arg.rsplit_once(':')?;
+    let last: usize = last.parse().ok()?;
+    if let Some((path, line)) = rest.rsplit_once(':')
+        && let Ok(line) = line.parse::<usize>()
+        && !path.is_empty()
+    {
+        return Some((path, line, Some(last)));
+    }
+    (!rest.is_empty()).then_some((rest, last, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliCommand, String> {
+        CliOptions::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    fn files(args: &[&str]) -> Vec<FileArg> {
+        match parse(args) {
+            Ok(CliCommand::Run(options)) => options.files,
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    fn file(path: &str, line: Option<usize>, column: Option<usize>) -> FileArg {
+        FileArg {
+            path: Some(PathBuf::from(path)),
+            line,
+            column,
+        }
+    }
+
+    #[test]
+    fn plus_line_applies_to_the_next_file_only() {
+        assert_eq!(
+            files(&["+7", "a.txt", "b.txt"]),
+            vec![file("a.txt", Some(7), None), file("b.txt", None, None)]
+        );
+    }
+
+    #[test]
+    fn splits_line_and_column_off_missing_files() {
+        assert_eq!(
+            files(&["missing.rs:12:3", "other.rs:4"]),
+            vec![file("missing.rs", Some(12), Some(3)), file("other.rs", Some(4), None)]
+        );
+        assert_eq!(files(&["missing.rs:12:3"])[0].position(), Some((11, 2)));
+    }
+
+    #[test]
+    fn keeps_an_existing_file_named_like_a_position() {
+        let dir = std::env::temp_dir().join(format!("kappa-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let literal = dir.join("a:1");
+        std::fs::write(&literal, "").unwrap();
+        let arg = literal.to_string_lossy().into_owned();
+
+        let parsed = files(&[&arg]);
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(parsed, vec![file(&arg, None, None)]);
+    }
+
+    #[test]
+    fn stdin_can_only_be_read_once() {
+        assert!(matches!(
+            parse(&["-"]),
+            Ok(CliCommand::Run(options)) if options.reads_stdin()
+        ));
+        assert!(parse(&["-", "-"]).is_err());
+    }
+
+    #[test]
+    fn session_cannot_be_combined_with_files() {
+        assert!(parse(&["-S"]).is_ok());
+        assert!(parse(&["-S", "a.txt"]).is_err());
+    }
+
+    #[test]
+    fn dangling_plus_line_is_an_error() {
+        assert!(parse(&["a.txt", "+3"]).is_err());
+        assert!(parse(&["+x", "a.txt"]).is_err());
+    }
+}
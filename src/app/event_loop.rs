@@ -0,0 +1,261 @@
+use crate::app::{AppEvent, FileWatcher, TaskSpawner, TimerKind, Timers};
+use crate::core::cursor::CursorMovement;
+use crate::core::document::SwapFile;
+use crate::features::file_operations::{AutoSave, ReloadFile};
+use crate::features::session::{FilePositions, Session};
+use crate::input::{InputAction, InputHandler};
+use crate::state::{Confirmation, EditorMode, EditorState};
+use crossterm::event::{self, Event};
+use ratatui::Terminal;
+use ratatui::backend::Backend;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const SWAP_INTERVAL: Duration = Duration::from_secs(2);
+const MESSAGE_TTL: Duration = Duration::from_secs(5);
+const EXPLORER_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const IDLE_WAIT: Duration = Duration::from_secs(60);
+
+pub struct EventLoop {
+    sender: Sender<AppEvent>,
+    receiver: Receiver<AppEvent>,
+    timers: Timers,
+    stop_input: Arc<AtomicBool>,
+    input_reader: Option<JoinHandle<()>>,
+}
+
+impl EventLoop {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender,
+            receiver,
+            timers: Timers::new(),
+            stop_input: Arc::new(AtomicBool::new(false)),
+            input_reader: None,
+        }
+    }
+
+    pub fn spawner(&self) -> TaskSpawner {
+        TaskSpawner::new(self.sender.clone())
+    }
+
+    pub fn run<B: Backend>(
+        mut self,
+        terminal: &mut Terminal<B>,
+        state: &mut EditorState,
+    ) -> io::Result<()> {
+        state.set_tasks(self.spawner());
+        self.spawn_input_reader();
+        let watcher = FileWatcher::spawn(self.sender.clone());
+
+        let mut needs_redraw = true;
+        loop {
+            if needs_redraw {
+                state.update_highlights();
+                terminal.draw(|f| crate::ui::render(f, state))?;
+                needs_redraw = false;
+            }
+            watcher.watch(state.document().file_path().cloned());
+
+            let timeout = self
+                .timers
+                .time_until_next(Instant::now())
+                .unwrap_or(IDLE_WAIT);
+            match self.receiver.recv_timeout(timeout) {
+                Ok(event) => {
+                    needs_redraw = true;
+                    let mut quit = self.dispatch(event, state)?;
+                    while !quit && let Ok(event) = self.receiver.try_recv() {
+                        quit = self.dispatch(event, state)?;
+                    }
+                    if quit {
+                        break;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            for timer in self.timers.take_expired(Instant::now()) {
+                needs_redraw = true;
+                self.fire(timer, state);
+                state.document_mut().commit_changes();
+            }
+            self.schedule_timers(state);
+        }
+
+        self.shutdown(state)
+    }
+
+    fn dispatch(&mut self, event: AppEvent, state: &mut EditorState) -> io::Result<bool> {
+        match event {
+            AppEvent::Input(event) => {
+                if let Event::Resize(_, height) = event {
+                    state
+                        .viewport_mut()
+                        .set_height((height as usize).saturating_sub(2));
+                }
+                if matches!(event, Event::Key(_) | Event::Paste(_))
+                    && let Some(delay) = state.auto_save().idle_delay
+                {
+                    self.timers.schedule_in(TimerKind::AutoSave, delay);
+                }
+                if let Some(InputAction::Quit) = InputHandler::handle(event, state)? {
+                    return Ok(true);
+                }
+            }
+            AppEvent::FileChanged(path) => Self::file_changed(path, state),
+            AppEvent::Message(message) => state.message_mut().set(message),
+            AppEvent::Update(update) => update(state),
+            AppEvent::Quit => return Ok(true),
+        }
+        // Each event is one undo step, however many edits it made.
+        state.document_mut().commit_changes();
+        SwapFile::update_crash_snapshot(state.document());
+        Ok(false)
+    }
+
+    fn fire(&mut self, timer: TimerKind, state: &mut EditorState) {
+        match timer {
+            TimerKind::SwapSync => {
+                if state.document().needs_swap()
+                    && let Err(e) = SwapFile::write(state.document_mut())
+                {
+                    state
+                        .message_mut()
+                        .set(format!("Swap file write failed: {}", e));
+                }
+            }
+            TimerKind::AutoSave => {
+                if state.auto_save().idle_delay.is_some() {
+                    AutoSave::run(state);
+                }
+            }
+            TimerKind::MessageExpiry => {
+                let expired = state
+                    .message()
+                    .set_at()
+                    .is_some_and(|at| at.elapsed() >= MESSAGE_TTL);
+                if expired && *state.mode() == EditorMode::Normal {
+                    state.message_mut().clear();
+                }
+            }
+            TimerKind::ExplorerRefresh => {
+                if state.is_explorer_visible()
+                    && let Some(scan) = state.explorer_mut().begin_scan()
+                {
+                    self.spawner().spawn(move || scan.read(), |scanned, state| {
+                        state.explorer_mut().finish_scan(scanned);
+                    });
+                }
+            }
+        }
+    }
+
+    fn schedule_timers(&mut self, state: &EditorState) {
+        if state.document().needs_swap() {
+            self.timers.schedule_if_idle(TimerKind::SwapSync, SWAP_INTERVAL);
+        }
+        if state.auto_save().idle_delay.is_none() {
+            self.timers.cancel(TimerKind::AutoSave);
+        }
+        if state.is_explorer_visible() {
+            self.timers
+                .schedule_if_idle(TimerKind::ExplorerRefresh, EXPLORER_REFRESH_INTERVAL);
+        } else {
+            self.timers.cancel(TimerKind::ExplorerRefresh);
+        }
+        match state.message().set_at() {
+            Some(at) if *state.mode() == EditorMode::Normal => {
+                self.timers.schedule(TimerKind::MessageExpiry, at + MESSAGE_TTL);
+            }
+            _ => self.timers.cancel(TimerKind::MessageExpiry),
+        }
+    }
+
+    fn file_changed(path: PathBuf, state: &mut EditorState) {
+        if state.document().file_path() != Some(&path) {
+            return;
+        }
+        match ReloadFile::differs_from_disk(state.document()) {
+            Ok(false) => {}
+            Ok(true) if state.loading().is_some() => {
+                let message = format!(
+                    "{} changed on disk while loading; reopen it to see the changes",
+                    state.document().file_name()
+                );
+                state.message_mut().set(message);
+            }
+            Ok(true) if state.document().is_modified() => {
+                if *state.mode() == EditorMode::Normal {
+                    let file_name = state.document().file_name();
+                    state.confirm(Confirmation::reload(&file_name));
+                } else {
+                    let message = format!(
+                        "{} changed on disk; saving will overwrite it",
+                        state.document().file_name()
+                    );
+                    state.message_mut().set(message);
+                }
+            }
+            Ok(true) => match ReloadFile::execute(state.document_mut()) {
+                Ok(_) => {
+                    let (buffer, cursor) = state.buffer_cursor();
+                    CursorMovement::clamp(cursor, buffer);
+                    let message = format!("Reloaded: {}", state.document().file_name());
+                    state.message_mut().set(message);
+                }
+                Err(e) => state.message_mut().set(format!("Reload failed: {}", e)),
+            },
+            Err(e) => {
+                state
+                    .message_mut()
+                    .set(format!("{} is no longer readable: {}", path.display(), e));
+            }
+        }
+    }
+
+    fn spawn_input_reader(&mut self) {
+        let sender = self.sender.clone();
+        let stop = Arc::clone(&self.stop_input);
+        self.input_reader = Some(thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                match event::poll(INPUT_POLL_INTERVAL) {
+                    Ok(true) => match event::read() {
+                        Ok(event) => {
+                            if sender.send(AppEvent::Input(event)).is_err() {
+                                break;
+                            }
+                        }
+                        Err(_) => break,
+                    },
+                    Ok(false) => {}
+                    Err(_) => break,
+                }
+            }
+        }));
+    }
+
+    fn shutdown(&mut self, state: &mut EditorState) -> io::Result<()> {
+        self.stop_input.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.input_reader.take() {
+            let _ = handle.join();
+        }
+        let _ = FilePositions::record_current(state);
+        let _ = Session::save_current(state);
+        SwapFile::remove(state.document_mut())
+    }
+}
+
+impl Default for EventLoop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
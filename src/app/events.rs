@@ -0,0 +1,13 @@
+use crate::state::EditorState;
+use crossterm::event::Event;
+use std::path::PathBuf;
+
+pub type StateUpdate = Box<dyn FnOnce(&mut EditorState) + Send>;
+
+pub enum AppEvent {
+    Input(Event),
+    FileChanged(PathBuf),
+    Message(String),
+    Update(StateUpdate),
+    Quit,
+}
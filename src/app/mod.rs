@@ -0,0 +1,13 @@
+mod cli;
+mod event_loop;
+mod events;
+mod tasks;
+mod timers;
+mod watcher;
+
+pub use cli::{CliCommand, CliOptions, FileArg, USAGE};
+pub use event_loop::EventLoop;
+pub use events::AppEvent;
+pub use tasks::TaskSpawner;
+pub use timers::{TimerKind, Timers};
+pub use watcher::FileWatcher;
@@ -0,0 +1,43 @@
+use crate::app::AppEvent;
+use crate::state::EditorState;
+use std::sync::mpsc::Sender;
+use std::thread;
+
+#[derive(Clone)]
+pub struct TaskSpawner {
+    sender: Sender<AppEvent>,
+}
+
+impl TaskSpawner {
+    pub fn new(sender: Sender<AppEvent>) -> Self {
+        Self { sender }
+    }
+
+    pub fn post(&self, event: AppEvent) -> bool {
+        self.sender.send(event).is_ok()
+    }
+
+    pub fn message(&self, message: String) -> bool {
+        self.post(AppEvent::Message(message))
+    }
+
+    pub fn update<F>(&self, update: F) -> bool
+    where
+        F: FnOnce(&mut EditorState) + Send + 'static,
+    {
+        self.post(AppEvent::Update(Box::new(update)))
+    }
+
+    pub fn spawn<W, R, F>(&self, work: W, then: F)
+    where
+        W: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+        F: FnOnce(R, &mut EditorState) + Send + 'static,
+    {
+        let sender = self.sender.clone();
+        thread::spawn(move || {
+            let result = work();
+            let _ = sender.send(AppEvent::Update(Box::new(move |state| then(result, state))));
+        });
+    }
+}
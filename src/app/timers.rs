@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimerKind {
+    SwapSync,
+    AutoSave,
+    MessageExpiry,
+    ExplorerRefresh,
+}
+
+pub struct Timers {
+    deadlines: HashMap<TimerKind, Instant>,
+}
+
+impl Timers {
+    pub fn new() -> Self {
+        Self {
+            deadlines: HashMap::new(),
+        }
+    }
+
+    pub fn schedule(&mut self, kind: TimerKind, at: Instant) {
+        self.deadlines.insert(kind, at);
+    }
+
+    pub fn schedule_in(&mut self, kind: TimerKind, delay: Duration) {
+        self.schedule(kind, Instant::now() + delay);
+    }
+
+    pub fn schedule_if_idle(&mut self, kind: TimerKind, delay: Duration) {
+        self.deadlines
+            .entry(kind)
+            .or_insert_with(|| Instant::now() + delay);
+    }
+
+    pub fn cancel(&mut self, kind: TimerKind) {
+        self.deadlines.remove(&kind);
+    }
+
+    pub fn is_scheduled(&self, kind: TimerKind) -> bool {
+        self.deadlines.contains_key(&kind)
+    }
+
+    pub fn time_until_next(&self, now: Instant) -> Option<Duration> {
+        self.deadlines
+            .values()
+            .min()
+            .map(|at| at.saturating_duration_since(now))
+    }
+
+    pub fn take_expired(&mut self, now: Instant) -> Vec<TimerKind> {
+        let expired: Vec<TimerKind> = self
+            .deadlines
+            .iter()
+            .filter(|(_, at)| **at <= now)
+            .map(|(kind, _)| *kind)
+            .collect();
+        for kind in &expired {
+            self.deadlines.remove(kind);
+        }
+        expired
+    }
+}
+
+impl Default for Timers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
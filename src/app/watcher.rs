@@ -0,0 +1,68 @@
+use crate::app::AppEvent;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct FileWatcher {
+    target: Arc<Mutex<Option<PathBuf>>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl FileWatcher {
+    pub fn spawn(sender: Sender<AppEvent>) -> Self {
+        let target: Arc<Mutex<Option<PathBuf>>> = Arc::new(Mutex::new(None));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread_target = Arc::clone(&target);
+        let thread_stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut watched: Option<(PathBuf, Option<SystemTime>)> = None;
+            while !thread_stop.load(Ordering::Relaxed) {
+                let current = thread_target.lock().ok().and_then(|t| t.clone());
+                match (&mut watched, current) {
+                    (Some((path, last)), Some(current)) if *path == current => {
+                        let mtime = Self::mtime(path);
+                        if mtime != *last {
+                            *last = mtime;
+                            if sender.send(AppEvent::FileChanged(path.clone())).is_err() {
+                                break;
+                            }
+                        }
+                    }
+                    (_, Some(current)) => {
+                        let mtime = Self::mtime(&current);
+                        watched = Some((current, mtime));
+                    }
+                    (_, None) => watched = None,
+                }
+                thread::sleep(WATCH_INTERVAL);
+            }
+        });
+
+        Self { target, stop }
+    }
+
+    pub fn watch(&self, path: Option<PathBuf>) {
+        if let Ok(mut target) = self.target.lock()
+            && *target != path
+        {
+            *target = path;
+        }
+    }
+
+    fn mtime(path: &PathBuf) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
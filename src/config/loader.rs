@@ -0,0 +1,108 @@
+use super::Config;
+use crate::ui::{Theme, ThemeLoader};
+use crate::utils::paths;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml::Table;
+
+pub const USER_FILE: &str = "config.toml";
+pub const PROJECT_FILE: &str = ".kappa.toml";
+
+#[derive(Debug, Clone, Default)]
+pub struct LoadedConfig {
+    pub config: Config,
+    pub theme: Theme,
+    pub sources: Vec<PathBuf>,
+    pub errors: Vec<String>,
+}
+
+impl LoadedConfig {
+    pub fn error_message(&self) -> Option<String> {
+        let first = self.errors.first()?;
+        Some(match self.errors.len() {
+            1 => format!("Config: {}", first),
+            n => format!("Config: {} (+{} more)", first, n - 1),
+        })
+    }
+}
+
+pub struct ConfigLoader;
+
+impl ConfigLoader {
+    pub fn user_path() -> Option<PathBuf> {
+        paths::config_dir().map(|d| d.join(USER_FILE))
+    }
+
+    pub fn project_path(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|d| d.join(PROJECT_FILE))
+            .find(|p| p.is_file())
+    }
+
+    /// Reads the user config, then lets the project file override it key by
+    /// key. A file that fails to parse is skipped as a whole.
+    pub fn load(dir: &Path) -> LoadedConfig {
+        let mut loaded = LoadedConfig::default();
+        let mut merged = Table::new();
+        let candidates = [Self::user_path(), Self::project_path(dir)];
+        for path in candidates.into_iter().flatten() {
+            match Self::read_table(&path) {
+                Ok(Some(table)) => {
+                    merge(&mut merged, table);
+                    loaded.sources.push(path);
+                }
+                Ok(None) => {}
+                Err(e) => loaded.errors.push(e),
+            }
+        }
+
+        loaded.config = toml::Value::Table(merged).try_into().unwrap_or_default();
+        loaded.errors.extend(loaded.config.validate());
+        loaded.theme = ThemeLoader::load(&loaded.config.ui.theme).unwrap_or_else(|e| {
+            loaded.errors.push(format!("ui.theme: {}", e));
+            Theme::default()
+        });
+        loaded
+    }
+
+    fn read_table(path: &Path) -> Result<Option<Table>, String> {
+        let name = display_name(path);
+        let raw = match fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("{}: {}", name, e)),
+        };
+        let describe = |e: toml::de::Error| {
+            let line = e.span().map(|s| raw[..s.start].matches('\n').count() + 1);
+            match line {
+                Some(line) => format!("{}:{}: {}", name, line, e.message()),
+                None => format!("{}: {}", name, e.message()),
+            }
+        };
+        let table: Table = raw.parse().map_err(describe)?;
+        // Check the shape per file so errors name the file they came from.
+        toml::from_str::<Config>(&raw).map_err(describe)?;
+        Ok(Some(table))
+    }
+}
+
+fn merge(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(table)) => {
+                merge(existing, table)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn display_name(path: &Path) -> String {
+    match paths::home_dir().and_then(|home| path.strip_prefix(home).ok()) {
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
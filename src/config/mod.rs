@@ -0,0 +1,5 @@
+mod loader;
+mod settings;
+
+pub use loader::{ConfigLoader, LoadedConfig};
+pub use settings::{ColorMode, Config, EditorSettings, UiSettings};
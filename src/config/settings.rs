@@ -0,0 +1,151 @@
+use crate::features::editing::AutoPairs;
+use crate::features::file_operations::AutoSaveSettings;
+use crate::features::navigation::BracketPairs;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+const MAX_TAB_WIDTH: usize = 16;
+const MIN_EXPLORER_WIDTH: u16 = 12;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub editor: EditorSettings,
+    pub ui: UiSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EditorSettings {
+    pub tab_width: usize,
+    pub insert_spaces: bool,
+    pub wrap: bool,
+    pub line_numbers: bool,
+    pub scroll_margin: usize,
+    pub auto_save: bool,
+    pub auto_save_delay: u64,
+    pub bracket_pairs: String,
+    pub auto_close: String,
+    /// Openers not auto-closed, by language name: `{ rust = "'" }`.
+    pub auto_close_except: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiSettings {
+    pub theme: String,
+    pub welcome_message: String,
+    pub explorer: bool,
+    pub explorer_width: u16,
+    pub recent_on_start: bool,
+    pub colors: ColorMode,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum ColorMode {
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    Ansi16,
+    #[serde(rename = "none")]
+    Monochrome,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            tab_width: 4,
+            insert_spaces: true,
+            wrap: false,
+            line_numbers: true,
+            scroll_margin: 0,
+            auto_save: false,
+            auto_save_delay: AutoSaveSettings::DEFAULT_IDLE_DELAY.as_secs(),
+            bracket_pairs: "()[]{}<>".to_string(),
+            auto_close: "()[]{}\"\"''".to_string(),
+            // Lifetimes outnumber char literals.
+            auto_close_except: BTreeMap::from([("rust".to_string(), "'".to_string())]),
+        }
+    }
+}
+
+impl Default for UiSettings {
+    fn default() -> Self {
+        Self {
+            theme: "dark".to_string(),
+            welcome_message: "Ctrl+P: Command Palette | Ctrl+S: Save | Ctrl+Q: Quit".to_string(),
+            explorer: false,
+            explorer_width: 32,
+            recent_on_start: false,
+            colors: ColorMode::Auto,
+        }
+    }
+}
+
+impl Config {
+    pub fn validate(&mut self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let editor = EditorSettings::default();
+        let ui = UiSettings::default();
+
+        if self.editor.tab_width == 0 || self.editor.tab_width > MAX_TAB_WIDTH {
+            errors.push(format!(
+                "editor.tab_width must be between 1 and {}",
+                MAX_TAB_WIDTH
+            ));
+            self.editor.tab_width = editor.tab_width;
+        }
+        if self.editor.auto_save_delay == 0 {
+            errors.push("editor.auto_save_delay must be at least 1 second".to_string());
+            self.editor.auto_save_delay = editor.auto_save_delay;
+        }
+        if let Err(e) = BracketPairs::parse(&self.editor.bracket_pairs) {
+            errors.push(format!("editor.bracket_pairs {}", e));
+            self.editor.bracket_pairs = editor.bracket_pairs;
+        }
+        if let Err(e) = AutoPairs::parse(&self.editor.auto_close) {
+            errors.push(format!("editor.auto_close {}", e));
+            self.editor.auto_close = editor.auto_close;
+        }
+        if self.ui.explorer_width < MIN_EXPLORER_WIDTH {
+            errors.push(format!(
+                "ui.explorer_width must be at least {}",
+                MIN_EXPLORER_WIDTH
+            ));
+            self.ui.explorer_width = ui.explorer_width;
+        }
+        errors
+    }
+}
+
+impl EditorSettings {
+    pub fn bracket_pairs(&self) -> BracketPairs {
+        BracketPairs::parse(&self.bracket_pairs).unwrap_or_default()
+    }
+
+    pub fn auto_pairs(&self, language: &str) -> AutoPairs {
+        let except = self
+            .auto_close_except
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(language))
+            .map_or("", |(_, openers)| openers.as_str());
+        AutoPairs::parse(&self.auto_close).unwrap_or_default().without(except)
+    }
+
+    pub fn auto_save_settings(&self) -> AutoSaveSettings {
+        if self.auto_save {
+            AutoSaveSettings {
+                idle_delay: Some(Duration::from_secs(self.auto_save_delay)),
+                on_focus_lost: true,
+            }
+        } else {
+            AutoSaveSettings::off()
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use super::RopeBuffer;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub start: usize,
+    pub removed: String,
+    pub inserted: String,
+}
+
+impl Change {
+    /// A single typed char, which is merged with the typing before it.
+    fn is_typing(&self) -> bool {
+        let mut chars = self.inserted.chars();
+        self.removed.is_empty()
+            && chars.next().is_some_and(|ch| !ch.is_whitespace())
+            && chars.next().is_none()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EditHistory {
+    undo: Vec<Vec<Change>>,
+    redo: Vec<Vec<Change>>,
+}
+
+impl EditHistory {
+    pub fn record(&mut self, changes: Vec<Change>) {
+        if changes.is_empty() {
+            return;
+        }
+        if let [change] = changes.as_slice()
+            && change.is_typing()
+            && self.redo.is_empty()
+            && let Some(step) = self.undo.last_mut()
+            && step.last().is_some_and(|last| last.is_typing() && last.start + 1 == change.start)
+        {
+            step.extend(changes);
+            return;
+        }
+        self.redo.clear();
+        self.undo.push(changes);
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn undo(&mut self, buffer: &mut RopeBuffer) -> Option<usize> {
+        let step = self.undo.pop()?;
+        for change in step.iter().rev() {
+            let end = change.start + change.inserted.chars().count();
+            buffer.replace(change.start..end, &change.removed);
+        }
+        buffer.take_changes();
+        let first = &step[0];
+        let cursor = first.start + first.removed.chars().count();
+        self.redo.push(step);
+        Some(cursor)
+    }
+
+    pub fn redo(&mut self, buffer: &mut RopeBuffer) -> Option<usize> {
+        let step = self.redo.pop()?;
+        for change in &step {
+            let end = change.start + change.removed.chars().count();
+            buffer.replace(change.start..end, &change.inserted);
+        }
+        buffer.take_changes();
+        let last = &step[step.len() - 1];
+        let cursor = last.start + last.inserted.chars().count();
+        self.undo.push(step);
+        Some(cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(buffer: &RopeBuffer) -> String {
+        buffer.get_slice(0, buffer.len_chars())
+    }
+
+    fn type_str(buffer: &mut RopeBuffer, history: &mut EditHistory, at: usize, typed: &str) {
+        for (i, ch) in typed.chars().enumerate() {
+            buffer.insert_char(at + i, ch);
+            history.record(buffer.take_changes());
+        }
+    }
+
+    #[test]
+    fn typed_chars_merge_into_one_step() {
+        let mut buffer = RopeBuffer::from_string("");
+        let mut history = EditHistory::default();
+        type_str(&mut buffer, &mut history, 0, "ab");
+        type_str(&mut buffer, &mut history, 2, " ");
+        type_str(&mut buffer, &mut history, 3, "cd");
+        assert_eq!(text(&buffer), "ab cd");
+
+        assert_eq!(history.undo(&mut buffer), Some(3));
+        assert_eq!(text(&buffer), "ab ");
+        assert_eq!(history.undo(&mut buffer), Some(2));
+        assert_eq!(text(&buffer), "ab");
+        assert_eq!(history.undo(&mut buffer), Some(0));
+        assert_eq!(text(&buffer), "");
+        assert_eq!(history.undo(&mut buffer), None);
+    }
+
+    #[test]
+    fn typing_elsewhere_starts_a_new_step() {
+        let mut buffer = RopeBuffer::from_string("xy");
+        let mut history = EditHistory::default();
+        type_str(&mut buffer, &mut history, 1, "a");
+        type_str(&mut buffer, &mut history, 0, "b");
+        assert_eq!(text(&buffer), "bxay");
+        history.undo(&mut buffer);
+        assert_eq!(text(&buffer), "xay");
+    }
+
+    #[test]
+    fn redo_reapplies_and_new_edits_clear_it() {
+        let mut buffer = RopeBuffer::from_string("one");
+        let mut history = EditHistory::default();
+        buffer.replace(0..3, "two");
+        history.record(buffer.take_changes());
+        history.undo(&mut buffer);
+        assert_eq!(text(&buffer), "one");
+        assert_eq!(history.redo(&mut buffer), Some(3));
+        assert_eq!(text(&buffer), "two");
+
+        history.undo(&mut buffer);
+        type_str(&mut buffer, &mut history, 3, "!");
+        assert_eq!(history.redo(&mut buffer), None);
+        assert_eq!(text(&buffer), "one!");
+    }
+
+    #[test]
+    fn typing_after_undo_does_not_merge_into_the_undone_step() {
+        let mut buffer = RopeBuffer::from_string("");
+        let mut history = EditHistory::default();
+        type_str(&mut buffer, &mut history, 0, "ab");
+        buffer.insert(2, "\n");
+        history.record(buffer.take_changes());
+        history.undo(&mut buffer);
+        type_str(&mut buffer, &mut history, 2, "c");
+        history.undo(&mut buffer);
+        assert_eq!(text(&buffer), "ab");
+    }
+
+    #[test]
+    fn multi_change_steps_undo_in_reverse() {
+        let mut buffer = RopeBuffer::from_string("abc");
+        let mut history = EditHistory::default();
+        buffer.insert(3, ")");
+        buffer.insert(0, "(");
+        buffer.remove(2..3);
+        history.record(buffer.take_changes());
+        assert_eq!(text(&buffer), "(ac)");
+        assert_eq!(history.undo(&mut buffer), Some(3));
+        assert_eq!(text(&buffer), "abc");
+        assert!(buffer.take_changes().is_empty());
+    }
+
+    #[test]
+    fn set_text_is_not_recorded() {
+        let mut buffer = RopeBuffer::from_string("a");
+        buffer.insert(1, "b");
+        buffer.set_text("loaded");
+        assert!(buffer.take_changes().is_empty());
+        assert_eq!(text(&buffer), "loaded");
+    }
+}
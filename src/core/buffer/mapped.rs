@@ -0,0 +1,106 @@
+use memmap2::Mmap;
+use std::fs::File;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
+
+/// Every `LINE_INDEX_STRIDE`-th line start is recorded with its char offset;
+/// lines in between are found by scanning forward, which keeps the index small
+/// for huge files.
+const LINE_INDEX_STRIDE: usize = 64;
+
+/// Only valid while no other process truncates the file, so it is reserved
+/// for large files opened read-only.
+pub struct MappedText {
+    map: Mmap,
+    len_lines: usize,
+    len_chars: usize,
+    checkpoints: Vec<(usize, usize)>,
+}
+
+impl MappedText {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only; see the type-level note on
+        // concurrent truncation.
+        let map = unsafe { Mmap::map(&file)? };
+        std::str::from_utf8(&map)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut checkpoints = vec![(0, 0)];
+        let mut len_lines = 1;
+        for newline in memchr::memchr_iter(b'\n', &map) {
+            if len_lines % LINE_INDEX_STRIDE == 0 {
+                let (byte, char_idx) = checkpoints[checkpoints.len() - 1];
+                let chars = count_chars(&map[byte..newline + 1]);
+                checkpoints.push((newline + 1, char_idx + chars));
+            }
+            len_lines += 1;
+        }
+        let (byte, char_idx) = checkpoints[checkpoints.len() - 1];
+        let len_chars = char_idx + count_chars(&map[byte..]);
+
+        Ok(Self {
+            map,
+            len_lines,
+            len_chars,
+            checkpoints,
+        })
+    }
+
+    pub fn text(&self) -> &str {
+        // SAFETY: validated as UTF-8 in `open`, and the mapping is read-only.
+        unsafe { std::str::from_utf8_unchecked(&self.map) }
+    }
+
+    pub fn len_lines(&self) -> usize {
+        self.len_lines
+    }
+
+    pub fn len_chars(&self) -> usize {
+        self.len_chars
+    }
+
+    pub fn line(&self, line_idx: usize) -> Option<&str> {
+        self.line_range(line_idx).map(|range| &self.text()[range])
+    }
+
+    pub fn line_to_char(&self, line_idx: usize) -> usize {
+        if line_idx >= self.len_lines {
+            return self.len_chars;
+        }
+        let (byte, char_idx) = self.checkpoints[line_idx / LINE_INDEX_STRIDE];
+        let start = self.line_range(line_idx).map_or(byte, |range| range.start);
+        char_idx + count_chars(&self.map[byte..start])
+    }
+
+    pub fn char_to_line(&self, char_idx: usize) -> usize {
+        let checkpoint = self
+            .checkpoints
+            .partition_point(|&(_, start)| start <= char_idx)
+            .saturating_sub(1);
+        let (byte, start) = self.checkpoints[checkpoint];
+        let end = self.text()[byte..]
+            .char_indices()
+            .nth(char_idx - start)
+            .map_or(self.map.len(), |(i, _)| byte + i);
+        checkpoint * LINE_INDEX_STRIDE + memchr::memchr_iter(b'\n', &self.map[byte..end]).count()
+    }
+
+    fn line_range(&self, line_idx: usize) -> Option<Range<usize>> {
+        if line_idx >= self.len_lines {
+            return None;
+        }
+        let bytes: &[u8] = &self.map;
+        let mut start = self.checkpoints[line_idx / LINE_INDEX_STRIDE].0;
+        for _ in 0..line_idx % LINE_INDEX_STRIDE {
+            start += memchr::memchr(b'\n', &bytes[start..])? + 1;
+        }
+        let end = memchr::memchr(b'\n', &bytes[start..]).map_or(bytes.len(), |i| start + i + 1);
+        Some(start..end)
+    }
+}
+
+fn count_chars(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|b| (**b as i8) >= -0x40).count()
+}
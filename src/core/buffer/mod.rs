@@ -1,5 +1,8 @@
+mod history;
+mod mapped;
 mod rope_buffer;
 mod operations;
 
-pub use rope_buffer::RopeBuffer;
+pub use history::{Change, EditHistory};
+pub use rope_buffer::{BufferEdit, RopeBuffer};
 pub use operations::TextOperation;
\ No newline at end of file
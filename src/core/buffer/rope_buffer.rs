@@ -1,78 +1,262 @@
-use ropey::Rope;
+use super::history::Change;
+use super::mapped::MappedText;
+use ropey::{Rope, RopeSlice};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
 
+#[derive(Clone)]
+enum Storage {
+    Rope(Rope),
+    Mapped(Arc<MappedText>),
+}
+
+/// One edit, as byte offsets and `(line, byte column)` points. The old end
+/// is measured before the edit and the new end after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferEdit {
+    pub start_byte: usize,
+    pub old_end_byte: usize,
+    pub new_end_byte: usize,
+    pub start: (usize, usize),
+    pub old_end: (usize, usize),
+    pub new_end: (usize, usize),
+}
+
+#[derive(Clone)]
 pub struct RopeBuffer {
-    rope: Rope,
+    storage: Storage,
+    edits: Vec<BufferEdit>,
+    changes: Vec<Change>,
 }
 
 impl RopeBuffer {
     pub fn new() -> Self {
-        Self { rope: Rope::new() }
+        Self {
+            storage: Storage::Rope(Rope::new()),
+            edits: Vec::new(),
+            changes: Vec::new(),
+        }
     }
 
     pub fn from_string(content: &str) -> Self {
         Self {
-            rope: Rope::from_str(content),
+            storage: Storage::Rope(Rope::from_str(content)),
+            edits: Vec::new(),
+            changes: Vec::new(),
         }
     }
 
+    pub fn from_reader<R: io::Read>(reader: R) -> io::Result<Self> {
+        Ok(Self {
+            storage: Storage::Rope(Rope::from_reader(reader)?),
+            edits: Vec::new(),
+            changes: Vec::new(),
+        })
+    }
+
+    /// Maps `path` into memory instead of copying it. The first edit copies
+    /// the text into a regular rope.
+    pub fn map_file(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            storage: Storage::Mapped(Arc::new(MappedText::open(path)?)),
+            edits: Vec::new(),
+            changes: Vec::new(),
+        })
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.storage, Storage::Mapped(_))
+    }
+
     pub fn len_lines(&self) -> usize {
-        self.rope.len_lines()
+        match &self.storage {
+            Storage::Rope(rope) => rope.len_lines(),
+            Storage::Mapped(mapped) => mapped.len_lines(),
+        }
     }
 
     pub fn len_chars(&self) -> usize {
-        self.rope.len_chars()
+        match &self.storage {
+            Storage::Rope(rope) => rope.len_chars(),
+            Storage::Mapped(mapped) => mapped.len_chars(),
+        }
     }
 
     pub fn insert_char(&mut self, idx: usize, ch: char) {
-        self.rope.insert_char(idx, ch);
+        let mut utf8 = [0; 4];
+        self.insert(idx, ch.encode_utf8(&mut utf8));
     }
 
     pub fn insert(&mut self, idx: usize, text: &str) {
-        self.rope.insert(idx, text);
+        self.replace(idx..idx, text);
     }
 
     pub fn remove(&mut self, range: std::ops::Range<usize>) {
-        if range.start < range.end && range.end <= self.rope.len_chars() {
-            self.rope.remove(range);
+        if range.start < range.end && range.end <= self.len_chars() {
+            self.replace(range, "");
         }
     }
 
-    pub fn line(&self, line_idx: usize) -> Option<String> {
-        if line_idx < self.rope.len_lines() {
-            Some(self.rope.line(line_idx).to_string())
-        } else {
-            None
+    pub fn replace(&mut self, range: std::ops::Range<usize>, text: &str) {
+        self.changes.push(Change {
+            start: range.start,
+            removed: self.get_slice(range.start, range.end),
+            inserted: text.to_string(),
+        });
+        self.splice(range, text);
+    }
+
+    /// Adds text read from disk or a pipe to the end. Not recorded for undo.
+    pub fn append(&mut self, text: &str) {
+        let end = self.len_chars();
+        self.splice(end..end, text);
+    }
+
+    fn splice(&mut self, range: std::ops::Range<usize>, text: &str) {
+        let rope = self.rope_mut();
+        let start_byte = rope.char_to_byte(range.start);
+        let old_end_byte = rope.char_to_byte(range.end);
+        let start = point(rope.slice(..), start_byte);
+        let old_end = point(rope.slice(..), old_end_byte);
+        rope.remove(range.clone());
+        rope.insert(range.start, text);
+        let new_end_byte = start_byte + text.len();
+        let new_end = point(rope.slice(..), new_end_byte);
+        self.edits.push(BufferEdit {
+            start_byte,
+            old_end_byte,
+            new_end_byte,
+            start,
+            old_end,
+            new_end,
+        });
+    }
+
+    pub fn take_edits(&mut self) -> Vec<BufferEdit> {
+        std::mem::take(&mut self.edits)
+    }
+
+    pub fn take_changes(&mut self) -> Vec<Change> {
+        std::mem::take(&mut self.changes)
+    }
+
+    pub fn line(&self, line_idx: usize) -> Option<RopeSlice<'_>> {
+        match &self.storage {
+            Storage::Rope(rope) if line_idx < rope.len_lines() => Some(rope.line(line_idx)),
+            Storage::Rope(_) => None,
+            Storage::Mapped(mapped) => mapped.line(line_idx).map(RopeSlice::from),
         }
     }
 
     pub fn line_len(&self, line_idx: usize) -> usize {
-        if line_idx < self.rope.len_lines() {
-            let line = self.rope.line(line_idx);
-            let line_str = line.to_string();
-            line_str.trim_end_matches(&['\n', '\r'][..]).len()
-        } else {
-            0
+        let Some(line) = self.line(line_idx) else {
+            return 0;
+        };
+        let mut len = line.len_chars();
+        while len > 0 && matches!(line.char(len - 1), '\n' | '\r') {
+            len -= 1;
         }
+        len
     }
 
     pub fn char_to_line(&self, char_idx: usize) -> usize {
-        self.rope.char_to_line(char_idx)
+        match &self.storage {
+            Storage::Rope(rope) => rope.char_to_line(char_idx),
+            Storage::Mapped(mapped) => mapped.char_to_line(char_idx),
+        }
     }
 
     pub fn line_to_char(&self, line_idx: usize) -> usize {
-        self.rope.line_to_char(line_idx)
+        match &self.storage {
+            Storage::Rope(rope) => rope.line_to_char(line_idx),
+            Storage::Mapped(mapped) => mapped.line_to_char(line_idx),
+        }
     }
 
     pub fn get_slice(&self, start: usize, end: usize) -> String {
-        if start < end && end <= self.rope.len_chars() {
-            self.rope.slice(start..end).to_string()
+        if start < end && end <= self.len_chars() {
+            self.slice().slice(start..end).to_string()
         } else {
             String::new()
         }
     }
 
-    pub fn chunks(&self) -> impl Iterator<Item = &str> {
-        self.rope.chunks()
+    pub fn chars_at(&self, char_idx: usize) -> ropey::iter::Chars<'_> {
+        self.slice().chars_at(char_idx)
+    }
+
+    pub fn chunks(&self) -> ropey::iter::Chunks<'_> {
+        self.slice().chunks()
+    }
+
+    /// Replaces all text with a new version from disk. Not recorded for
+    /// undo, and pending changes no longer apply, so they are dropped.
+    pub fn set_text(&mut self, content: &str) {
+        self.changes.clear();
+        let old_end_byte = self.len_bytes();
+        let old_end = point(self.slice(), old_end_byte);
+        self.storage = Storage::Rope(Rope::from_str(content));
+        self.edits.push(BufferEdit {
+            start_byte: 0,
+            old_end_byte,
+            new_end_byte: content.len(),
+            start: (0, 0),
+            old_end,
+            new_end: point(self.slice(), content.len()),
+        });
+    }
+
+    pub fn len_bytes(&self) -> usize {
+        self.slice().len_bytes()
+    }
+
+    pub fn char_to_byte(&self, char_idx: usize) -> usize {
+        self.slice().char_to_byte(char_idx)
+    }
+
+    pub fn byte_to_char(&self, byte_idx: usize) -> usize {
+        self.slice().byte_to_char(byte_idx)
+    }
+
+    pub fn chunk_at_byte(&self, byte_idx: usize) -> &str {
+        let slice = self.slice();
+        if byte_idx >= slice.len_bytes() {
+            return "";
+        }
+        let (chunk, chunk_start, _, _) = slice.chunk_at_byte(byte_idx);
+        &chunk[byte_idx - chunk_start..]
+    }
+
+    fn slice(&self) -> RopeSlice<'_> {
+        match &self.storage {
+            Storage::Rope(rope) => rope.slice(..),
+            Storage::Mapped(mapped) => RopeSlice::from(mapped.text()),
+        }
+    }
+
+    fn rope_mut(&mut self) -> &mut Rope {
+        if let Storage::Mapped(mapped) = &self.storage {
+            self.storage = Storage::Rope(Rope::from_str(mapped.text()));
+        }
+        match &mut self.storage {
+            Storage::Rope(rope) => rope,
+            Storage::Mapped(_) => unreachable!("mapped storage was just replaced"),
+        }
+    }
+}
+
+fn point(text: RopeSlice<'_>, byte_idx: usize) -> (usize, usize) {
+    let line = text.byte_to_line(byte_idx);
+    (line, byte_idx - text.line_to_byte(line))
+}
+
+impl std::fmt::Display for RopeBuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for chunk in self.chunks() {
+            f.write_str(chunk)?;
+        }
+        Ok(())
     }
 }
 
@@ -80,4 +264,4 @@ impl Default for RopeBuffer {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,7 @@
 mod position;
 mod movement;
+mod selection;
 
 pub use position::CursorPosition;
-pub use movement::CursorMovement;
\ No newline at end of file
+pub use movement::CursorMovement;
+pub use selection::Selection;
@@ -68,4 +68,9 @@ impl CursorMovement {
             cursor.column = line_len;
         }
     }
+
+    pub fn clamp(cursor: &mut CursorPosition, buffer: &RopeBuffer) {
+        cursor.line = cursor.line.min(buffer.len_lines().saturating_sub(1));
+        cursor.column = cursor.column.min(buffer.line_len(cursor.line));
+    }
 }
\ No newline at end of file
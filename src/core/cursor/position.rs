@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use crate::core::buffer::RopeBuffer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CursorPosition {
     pub line: usize,
     pub column: usize,
@@ -13,6 +15,11 @@ impl CursorPosition {
         Self { line, column }
     }
 
+    pub fn from_char_idx(buffer: &RopeBuffer, char_idx: usize) -> Self {
+        let line = buffer.char_to_line(char_idx);
+        Self::at(line, char_idx - buffer.line_to_char(line))
+    }
+
     pub fn move_to(&mut self, line: usize, column: usize) {
         self.line = line;
         self.column = column;
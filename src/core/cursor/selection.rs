@@ -0,0 +1,51 @@
+use super::CursorPosition;
+use crate::core::buffer::RopeBuffer;
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selection {
+    pub anchor: CursorPosition,
+    pub head: CursorPosition,
+}
+
+impl Selection {
+    pub fn new(anchor: CursorPosition, head: CursorPosition) -> Self {
+        Self { anchor, head }
+    }
+
+    pub fn from_char_range(buffer: &RopeBuffer, range: Range<usize>) -> Self {
+        Self {
+            anchor: CursorPosition::from_char_idx(buffer, range.end),
+            head: CursorPosition::from_char_idx(buffer, range.start),
+        }
+    }
+
+    pub fn start(&self) -> CursorPosition {
+        self.anchor.min(self.head)
+    }
+
+    pub fn end(&self) -> CursorPosition {
+        self.anchor.max(self.head)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.anchor == self.head
+    }
+
+    pub fn char_range(&self, buffer: &RopeBuffer) -> Range<usize> {
+        let (start, end) = (self.start(), self.end());
+        buffer.line_to_char(start.line) + start.column..buffer.line_to_char(end.line) + end.column
+    }
+
+    /// The selected columns of `line`; the line ending counts as one past
+    /// the last column.
+    pub fn columns_on(&self, line: usize) -> Option<Range<usize>> {
+        let (start, end) = (self.start(), self.end());
+        if line < start.line || line > end.line {
+            return None;
+        }
+        let from = if line == start.line { start.column } else { 0 };
+        let to = if line == end.line { end.column } else { usize::MAX };
+        Some(from..to)
+    }
+}
@@ -0,0 +1,115 @@
+use crate::utils::paths;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackupPolicy {
+    #[default]
+    Off,
+    Tilde,
+    Numbered(usize),
+}
+
+impl BackupPolicy {
+    pub const DEFAULT_NUMBERED: usize = 5;
+
+    pub fn next(self) -> Self {
+        match self {
+            BackupPolicy::Off => BackupPolicy::Tilde,
+            BackupPolicy::Tilde => BackupPolicy::Numbered(Self::DEFAULT_NUMBERED),
+            BackupPolicy::Numbered(_) => BackupPolicy::Off,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            BackupPolicy::Off => "off".to_string(),
+            BackupPolicy::Tilde => "keep file~".to_string(),
+            BackupPolicy::Numbered(n) => format!("{} numbered copies", n),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupEntry {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+}
+
+pub struct Backup;
+
+impl Backup {
+    pub fn backup_dir() -> Option<PathBuf> {
+        paths::state_dir().map(|d| d.join("backups"))
+    }
+
+    pub fn tilde_path(file_path: &Path) -> PathBuf {
+        let mut name = file_path.as_os_str().to_os_string();
+        name.push("~");
+        PathBuf::from(name)
+    }
+
+    pub fn numbered_path(dir: &Path, file_path: &Path, n: usize) -> PathBuf {
+        dir.join(format!("{}.{}~", paths::mangle(file_path), n))
+    }
+
+    pub fn create(file_path: &Path, policy: BackupPolicy) -> io::Result<()> {
+        if !file_path.is_file() {
+            return Ok(());
+        }
+        match policy {
+            BackupPolicy::Off => Ok(()),
+            BackupPolicy::Tilde => fs::copy(file_path, Self::tilde_path(file_path)).map(|_| ()),
+            BackupPolicy::Numbered(count) => {
+                let dir = Self::backup_dir().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotFound, "No state directory for backups")
+                })?;
+                fs::create_dir_all(&dir)?;
+                Self::rotate(&dir, file_path, count.max(1))?;
+                fs::copy(file_path, Self::numbered_path(&dir, file_path, 1)).map(|_| ())
+            }
+        }
+    }
+
+    pub fn list_for(file_path: &Path) -> Vec<BackupEntry> {
+        let mut entries = Vec::new();
+        let tilde = Self::tilde_path(file_path);
+        if tilde.is_file() {
+            entries.push(Self::entry(tilde));
+        }
+        if let Some(dir) = Self::backup_dir() {
+            let mut n = 1;
+            loop {
+                let path = Self::numbered_path(&dir, file_path, n);
+                if !path.is_file() {
+                    break;
+                }
+                entries.push(Self::entry(path));
+                n += 1;
+            }
+        }
+        entries.sort_by_key(|e| std::cmp::Reverse(e.modified));
+        entries
+    }
+
+    fn rotate(dir: &Path, file_path: &Path, count: usize) -> io::Result<()> {
+        let oldest = Self::numbered_path(dir, file_path, count);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for n in (1..count).rev() {
+            let from = Self::numbered_path(dir, file_path, n);
+            if from.exists() {
+                fs::rename(&from, Self::numbered_path(dir, file_path, n + 1))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn entry(path: PathBuf) -> BackupEntry {
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+        BackupEntry { path, modified }
+    }
+}
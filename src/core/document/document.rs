@@ -1,10 +1,36 @@
-use crate::core::buffer::RopeBuffer;
-use std::path::PathBuf;
+use crate::core::buffer::{BufferEdit, EditHistory, RopeBuffer};
+use crate::core::document::DocumentSettings;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiskStamp {
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl DiskStamp {
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let metadata = fs::metadata(path)?;
+        Ok(Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
 
 pub struct Document {
     buffer: RopeBuffer,
     file_path: Option<PathBuf>,
     modified: bool,
+    swap_stale: bool,
+    swap_written: bool,
+    read_only: bool,
+    disk_stamp: Option<DiskStamp>,
+    history: EditHistory,
+    settings: DocumentSettings,
 }
 
 impl Document {
@@ -13,6 +39,12 @@ impl Document {
             buffer: RopeBuffer::new(),
             file_path: None,
             modified: false,
+            swap_stale: false,
+            swap_written: false,
+            read_only: false,
+            disk_stamp: None,
+            history: EditHistory::default(),
+            settings: DocumentSettings::default(),
         }
     }
 
@@ -21,6 +53,12 @@ impl Document {
             buffer,
             file_path: path,
             modified: false,
+            swap_stale: false,
+            swap_written: false,
+            read_only: false,
+            disk_stamp: None,
+            history: EditHistory::default(),
+            settings: DocumentSettings::default(),
         }
     }
 
@@ -28,11 +66,53 @@ impl Document {
         &self.buffer
     }
 
-    pub fn buffer_mut(&mut self) -> &mut RopeBuffer {
+    pub fn buffer_mut(&mut self) -> io::Result<&mut RopeBuffer> {
+        if self.read_only {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "Document is read-only",
+            ));
+        }
         self.modified = true;
+        self.swap_stale = true;
+        Ok(&mut self.buffer)
+    }
+
+    /// The buffer, for text read from disk or a pipe. Bypasses read-only and
+    /// does not mark the document modified.
+    pub fn load_buffer(&mut self) -> &mut RopeBuffer {
         &mut self.buffer
     }
 
+    /// Replaces the text with a new version from disk without marking the
+    /// document modified. Undo steps recorded against the old text are
+    /// dropped.
+    pub fn load_text(&mut self, text: &str) {
+        self.buffer.set_text(text);
+        self.history.clear();
+    }
+
+    pub fn take_edits(&mut self) -> Vec<BufferEdit> {
+        self.buffer.take_edits()
+    }
+
+    pub fn commit_changes(&mut self) {
+        let changes = self.buffer.take_changes();
+        self.history.record(changes);
+    }
+
+    pub fn undo(&mut self) -> io::Result<Option<usize>> {
+        self.commit_changes();
+        self.buffer_mut()?;
+        Ok(self.history.undo(&mut self.buffer))
+    }
+
+    pub fn redo(&mut self) -> io::Result<Option<usize>> {
+        self.commit_changes();
+        self.buffer_mut()?;
+        Ok(self.history.redo(&mut self.buffer))
+    }
+
     pub fn file_path(&self) -> Option<&PathBuf> {
         self.file_path.as_ref()
     }
@@ -41,16 +121,59 @@ impl Document {
         self.file_path = Some(path);
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn disk_stamp(&self) -> Option<DiskStamp> {
+        self.disk_stamp
+    }
+
+    pub fn set_disk_stamp(&mut self, stamp: Option<DiskStamp>) {
+        self.disk_stamp = stamp;
+    }
+
+    pub fn settings(&self) -> &DocumentSettings {
+        &self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: DocumentSettings) {
+        self.settings = settings;
+    }
+
     pub fn is_modified(&self) -> bool {
         self.modified
     }
 
     pub fn mark_saved(&mut self) {
         self.modified = false;
+        self.swap_stale = false;
     }
 
     pub fn mark_modified(&mut self) {
         self.modified = true;
+        self.swap_stale = true;
+    }
+
+    pub fn needs_swap(&self) -> bool {
+        self.modified && self.swap_stale && self.file_path.is_some()
+    }
+
+    pub fn has_swap(&self) -> bool {
+        self.swap_written
+    }
+
+    pub fn mark_swapped(&mut self) {
+        self.swap_stale = false;
+        self.swap_written = true;
+    }
+
+    pub fn mark_swap_removed(&mut self) {
+        self.swap_written = false;
     }
 
     pub fn file_name(&self) -> String {
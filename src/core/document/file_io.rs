@@ -1,41 +1,109 @@
 use crate::core::buffer::RopeBuffer;
-use crate::core::document::Document;
+use crate::core::document::{
+    Backup, BackupPolicy, Charset, DiskStamp, Document, DocumentSettings, SwapFile,
+};
+use std::borrow::Cow;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, BufReader, Write};
 use std::path::{Path, PathBuf};
 
 pub struct FileIO;
 
 impl FileIO {
-    pub fn load_from_file(path: &str) -> io::Result<Document> {
-        let content = fs::read_to_string(path)?;
-        let buffer = RopeBuffer::from_string(&content);
-        Ok(Document::from_buffer(buffer, Some(PathBuf::from(path))))
+    pub fn load_from_file(path: &str, settings: DocumentSettings) -> io::Result<Document> {
+        let buffer = match settings.charset {
+            Some(charset) if !charset.is_utf8() => {
+                RopeBuffer::from_string(&charset.decode(&fs::read(path)?))
+            }
+            charset => {
+                let file = fs::File::open(path)?;
+                let mut buffer = RopeBuffer::from_reader(BufReader::new(file))?;
+                if charset.is_some() && buffer.len_chars() > 0 && buffer.get_slice(0, 1) == "\u{feff}" {
+                    buffer.remove(0..1);
+                }
+                buffer
+            }
+        };
+        let mut document = Document::from_buffer(buffer, Some(PathBuf::from(path)));
+        document.set_read_only(!Self::is_writable(Path::new(path)));
+        document.set_settings(settings);
+        Ok(document)
     }
 
-    pub fn save_document(document: &mut Document) -> io::Result<()> {
+    pub fn read_text(path: &Path, charset: Option<Charset>) -> io::Result<String> {
+        match charset {
+            Some(charset) => Ok(charset.decode(&fs::read(path)?)),
+            None => fs::read_to_string(path),
+        }
+    }
+
+    pub fn map_file(path: &Path) -> io::Result<Document> {
+        let stamp = DiskStamp::read(path)?;
+        let buffer = RopeBuffer::map_file(path)?;
+        let mut document = Document::from_buffer(buffer, Some(path.to_path_buf()));
+        document.set_read_only(true);
+        document.set_disk_stamp(Some(stamp));
+        Ok(document)
+    }
+
+    pub fn is_writable(path: &Path) -> bool {
+        fs::OpenOptions::new().append(true).open(path).is_ok()
+    }
+
+    pub fn save_document(document: &mut Document, backup: BackupPolicy) -> io::Result<()> {
+        if document.is_read_only() {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "Document is read-only",
+            ));
+        }
         if let Some(path) = document.file_path() {
-            Self::write_to_file(document.buffer(), path)?;
+            let path = path.clone();
+            Backup::create(&path, backup)?;
+            Self::write_document(document, &path)?;
             document.mark_saved();
+            SwapFile::discard(document)?;
             Ok(())
         } else {
             Err(io::Error::new(io::ErrorKind::NotFound, "No file path set"))
         }
     }
 
-    pub fn save_document_as(document: &mut Document, path: &str) -> io::Result<()> {
+    pub fn save_document_as(
+        document: &mut Document,
+        path: &str,
+        backup: BackupPolicy,
+    ) -> io::Result<()> {
         let path_buf = PathBuf::from(path);
-        Self::write_to_file(document.buffer(), &path_buf)?;
+        Backup::create(&path_buf, backup)?;
+        Self::write_document(document, &path_buf)?;
+        SwapFile::discard(document)?;
         document.set_file_path(path_buf);
+        document.set_read_only(false);
         document.mark_saved();
         Ok(())
     }
 
-    fn write_to_file(buffer: &RopeBuffer, path: &Path) -> io::Result<()> {
+    /// Applies the document's save-time rewrites to the buffer, so it keeps
+    /// matching the disk, then writes it in the document's charset.
+    fn write_document(document: &mut Document, path: &Path) -> io::Result<()> {
+        let settings = document.settings().clone();
+        if settings.rewrites_on_save() {
+            let text = document.buffer().to_string();
+            if let Cow::Owned(normalized) = settings.normalize(&text) {
+                document.load_text(&normalized);
+            }
+        }
+
         let mut file = fs::File::create(path)?;
-        for chunk in buffer.chunks() {
-            file.write_all(chunk.as_bytes())?;
+        match settings.charset {
+            Some(charset) => file.write_all(&charset.encode(&document.buffer().to_string())?),
+            None => {
+                for chunk in document.buffer().chunks() {
+                    file.write_all(chunk.as_bytes())?;
+                }
+                Ok(())
+            }
         }
-        Ok(())
     }
 }
\ No newline at end of file
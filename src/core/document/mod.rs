@@ -1,5 +1,12 @@
+#[allow(clippy::module_inception)]
 mod document;
+mod backup;
 mod file_io;
+mod settings;
+mod swap;
 
-pub use document::Document;
-pub use file_io::FileIO;
\ No newline at end of file
+pub use backup::{Backup, BackupEntry, BackupPolicy};
+pub use document::{DiskStamp, Document};
+pub use file_io::FileIO;
+pub use settings::{Charset, DocumentSettings, IndentStyle, Indentation, LineEnding};
+pub use swap::{SwapContents, SwapFile};
\ No newline at end of file
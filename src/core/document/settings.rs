@@ -0,0 +1,301 @@
+use std::borrow::Cow;
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tab,
+    Space,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+    Cr,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "LF",
+            LineEnding::CrLf => "CRLF",
+            LineEnding::Cr => "CR",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Utf8,
+    Utf8Bom,
+    Latin1,
+    Utf16Le,
+    Utf16Be,
+}
+
+const BOM: char = '\u{feff}';
+
+impl Charset {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Charset::Utf8 => "utf-8",
+            Charset::Utf8Bom => "utf-8-bom",
+            Charset::Latin1 => "latin1",
+            Charset::Utf16Le => "utf-16le",
+            Charset::Utf16Be => "utf-16be",
+        }
+    }
+
+    pub fn is_utf8(&self) -> bool {
+        matches!(self, Charset::Utf8 | Charset::Utf8Bom)
+    }
+
+    /// Decodes file contents. Any byte order mark is dropped; invalid
+    /// sequences become U+FFFD.
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            Charset::Utf8 | Charset::Utf8Bom => {
+                let text = String::from_utf8_lossy(bytes);
+                text.strip_prefix(BOM).unwrap_or(&text).to_string()
+            }
+            Charset::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+            Charset::Utf16Le | Charset::Utf16Be => {
+                let units = bytes.chunks_exact(2).map(|pair| match self {
+                    Charset::Utf16Le => u16::from_le_bytes([pair[0], pair[1]]),
+                    _ => u16::from_be_bytes([pair[0], pair[1]]),
+                });
+                let text: String = char::decode_utf16(units)
+                    .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect();
+                text.strip_prefix(BOM).unwrap_or(&text).to_string()
+            }
+        }
+    }
+
+    pub fn encode(&self, text: &str) -> io::Result<Vec<u8>> {
+        let text = text.strip_prefix(BOM).unwrap_or(text);
+        match self {
+            Charset::Utf8 => Ok(text.as_bytes().to_vec()),
+            Charset::Utf8Bom => {
+                let mut out = BOM.to_string().into_bytes();
+                out.extend_from_slice(text.as_bytes());
+                Ok(out)
+            }
+            Charset::Latin1 => text
+                .chars()
+                .map(|c| {
+                    u8::try_from(c).map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("'{}' cannot be written as latin1", c),
+                        )
+                    })
+                })
+                .collect(),
+            Charset::Utf16Le | Charset::Utf16Be => {
+                let mut out = Vec::with_capacity(text.len() * 2 + 2);
+                for unit in std::iter::once(BOM as u16).chain(text.encode_utf16()) {
+                    let bytes = match self {
+                        Charset::Utf16Le => unit.to_le_bytes(),
+                        _ => unit.to_be_bytes(),
+                    };
+                    out.extend_from_slice(&bytes);
+                }
+                Ok(out)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Indentation {
+    pub size: usize,
+    pub tab_width: usize,
+    pub use_tabs: bool,
+}
+
+/// Per-file formatting, typically resolved from `.editorconfig`. `None` and
+/// `false` leave the file as it is, so `insert_final_newline = false` never
+/// strips an existing newline.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentSettings {
+    pub indent_style: Option<IndentStyle>,
+    pub indent_size: Option<usize>,
+    pub tab_width: Option<usize>,
+    pub end_of_line: Option<LineEnding>,
+    pub charset: Option<Charset>,
+    pub trim_trailing_whitespace: bool,
+    pub insert_final_newline: bool,
+    pub max_line_length: Option<usize>,
+}
+
+impl DocumentSettings {
+    pub fn indentation(&self, default_tab_width: usize, default_spaces: bool) -> Indentation {
+        let tab_width = self.tab_width.or(self.indent_size).unwrap_or(default_tab_width);
+        Indentation {
+            size: self.indent_size.unwrap_or(tab_width),
+            tab_width,
+            use_tabs: match self.indent_style {
+                Some(style) => style == IndentStyle::Tab,
+                None => !default_spaces,
+            },
+        }
+    }
+
+    pub fn rewrites_on_save(&self) -> bool {
+        self.trim_trailing_whitespace || self.end_of_line.is_some() || self.insert_final_newline
+    }
+
+    pub fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self.rewrites_on_save() {
+            return Cow::Borrowed(text);
+        }
+
+        let mut lines: Vec<(&str, &str)> = Vec::new();
+        let mut rest = text;
+        while !rest.is_empty() {
+            let (line, ending, next) = match rest.find(['\n', '\r']) {
+                Some(i) if rest[i..].starts_with("\r\n") => (&rest[..i], "\r\n", &rest[i + 2..]),
+                Some(i) => (&rest[..i], &rest[i..i + 1], &rest[i + 1..]),
+                None => (rest, "", ""),
+            };
+            lines.push((line, ending));
+            rest = next;
+        }
+
+        if self.insert_final_newline && lines.last().is_some_and(|(_, e)| e.is_empty()) {
+            // Match the ending already used by the file.
+            let ending = lines.iter().rev().map(|(_, e)| *e).find(|e| !e.is_empty());
+            if let Some(last) = lines.last_mut() {
+                last.1 = ending.unwrap_or("\n");
+            }
+        }
+
+        let mut out = String::with_capacity(text.len());
+        for (line, ending) in lines {
+            let line = if self.trim_trailing_whitespace {
+                line.trim_end_matches([' ', '\t'])
+            } else {
+                line
+            };
+            out.push_str(line);
+            match (self.end_of_line, ending.is_empty()) {
+                (Some(eol), false) => out.push_str(eol.as_str()),
+                _ => out.push_str(ending),
+            }
+        }
+        if out == text {
+            Cow::Borrowed(text)
+        } else {
+            Cow::Owned(out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHARSETS: [Charset; 5] = [
+        Charset::Utf8,
+        Charset::Utf8Bom,
+        Charset::Latin1,
+        Charset::Utf16Le,
+        Charset::Utf16Be,
+    ];
+
+    #[test]
+    fn charsets_round_trip_text() {
+        for charset in CHARSETS {
+            let text = "caf\u{e9} \u{ff}\n";
+            let bytes = charset.encode(text).unwrap();
+            assert_eq!(charset.decode(&bytes), text, "{}", charset.label());
+        }
+        for charset in [Charset::Utf8, Charset::Utf16Le, Charset::Utf16Be] {
+            let text = "\u{1f600} \u{4e2d}";
+            assert_eq!(charset.decode(&charset.encode(text).unwrap()), text);
+        }
+    }
+
+    #[test]
+    fn encode_writes_the_expected_byte_order_marks() {
+        assert_eq!(Charset::Utf8.encode("\u{feff}a").unwrap(), b"a");
+        assert_eq!(Charset::Utf8Bom.encode("a").unwrap(), b"\xef\xbb\xbfa");
+        assert_eq!(Charset::Utf16Le.encode("a").unwrap(), b"\xff\xfea\x00");
+        assert_eq!(Charset::Utf16Be.encode("a").unwrap(), b"\xfe\xff\x00a");
+        assert_eq!(Charset::Latin1.encode("\u{e9}").unwrap(), b"\xe9");
+        let err = Charset::Latin1.encode("\u{20ac}").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn decode_replaces_invalid_input() {
+        assert_eq!(Charset::Utf8.decode(b"a\xffb"), "a\u{fffd}b");
+        assert_eq!(Charset::Utf16Le.decode(&[0x00, 0xd8, b'a', 0x00]), "\u{fffd}a");
+    }
+
+    #[test]
+    fn normalize_leaves_text_alone_without_rewriting_settings() {
+        let settings = DocumentSettings {
+            indent_size: Some(2),
+            ..Default::default()
+        };
+        assert!(matches!(settings.normalize("a  \r\nb"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn normalize_trims_and_converts_line_endings() {
+        let settings = DocumentSettings {
+            trim_trailing_whitespace: true,
+            end_of_line: Some(LineEnding::Lf),
+            ..Default::default()
+        };
+        assert_eq!(settings.normalize("a \t\r\nb\rc  "), "a\nb\nc");
+        assert!(matches!(settings.normalize("a\nb"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn final_newline_matches_the_existing_ending() {
+        let settings = DocumentSettings {
+            insert_final_newline: true,
+            ..Default::default()
+        };
+        assert_eq!(settings.normalize("a\r\nb"), "a\r\nb\r\n");
+        assert_eq!(settings.normalize("a"), "a\n");
+        assert_eq!(settings.normalize("a\n"), "a\n");
+        assert_eq!(settings.normalize(""), "");
+
+        let settings = DocumentSettings {
+            insert_final_newline: true,
+            end_of_line: Some(LineEnding::CrLf),
+            ..Default::default()
+        };
+        assert_eq!(settings.normalize("a\nb"), "a\r\nb\r\n");
+    }
+
+    #[test]
+    fn indentation_falls_back_to_defaults() {
+        let settings = DocumentSettings {
+            indent_size: Some(2),
+            ..Default::default()
+        };
+        let indentation = settings.indentation(8, true);
+        assert_eq!((indentation.size, indentation.tab_width, indentation.use_tabs), (2, 2, false));
+
+        let settings = DocumentSettings {
+            indent_style: Some(IndentStyle::Tab),
+            tab_width: Some(4),
+            ..Default::default()
+        };
+        let indentation = settings.indentation(8, true);
+        assert_eq!((indentation.size, indentation.tab_width, indentation.use_tabs), (4, 4, true));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::core::buffer::RopeBuffer;
+use crate::core::document::Document;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const SWAP_MAGIC: &str = "KAPPA-SWP 1";
+const SWAP_SUFFIX: &str = ".kappa-swp";
+
+// Latest unsaved snapshot, kept so the panic hook can flush it without access
+// to the editor state. Rope clones share structure, so updating it is cheap.
+static CRASH_SNAPSHOT: Mutex<Option<(PathBuf, RopeBuffer)>> = Mutex::new(None);
+
+pub struct SwapContents {
+    pub pid: u32,
+    pub content: String,
+}
+
+impl SwapContents {
+    /// Whether the process that wrote the swap may still be editing the file.
+    /// Unknown owners, and systems without `/proc`, count as running.
+    pub fn owner_running(&self) -> bool {
+        if self.pid == std::process::id() {
+            return false;
+        }
+        let proc = Path::new("/proc");
+        self.pid == 0 || !proc.is_dir() || proc.join(self.pid.to_string()).exists()
+    }
+}
+
+pub struct SwapFile;
+
+impl SwapFile {
+    pub fn path_for(file_path: &Path) -> PathBuf {
+        let name = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unnamed");
+        let swap_name = format!(".{}{}", name, SWAP_SUFFIX);
+        match file_path.parent() {
+            Some(parent) => parent.join(swap_name),
+            None => PathBuf::from(swap_name),
+        }
+    }
+
+    pub fn exists_for(file_path: &Path) -> bool {
+        Self::path_for(file_path).is_file()
+    }
+
+    pub fn write(document: &mut Document) -> io::Result<()> {
+        let Some(path) = document.file_path() else {
+            return Ok(());
+        };
+        Self::write_buffer(path, document.buffer())?;
+        document.mark_swapped();
+        Ok(())
+    }
+
+    pub fn read(file_path: &Path) -> io::Result<SwapContents> {
+        let raw = fs::read_to_string(Self::path_for(file_path))?;
+        let mut parts = raw.splitn(4, '\n');
+        if parts.next() != Some(SWAP_MAGIC) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Not a kappa swap file",
+            ));
+        }
+        let _original = parts.next();
+        let pid = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+        let content = parts.next().unwrap_or("").to_string();
+        Ok(SwapContents { pid, content })
+    }
+
+    pub fn remove(document: &mut Document) -> io::Result<()> {
+        if !document.has_swap() {
+            return Ok(());
+        }
+        if let Some(path) = document.file_path() {
+            Self::remove_for(path)?;
+        }
+        document.mark_swap_removed();
+        Ok(())
+    }
+
+    pub fn discard(document: &mut Document) -> io::Result<()> {
+        if let Some(path) = document.file_path() {
+            Self::remove_for(path)?;
+        }
+        document.mark_swap_removed();
+        Ok(())
+    }
+
+    pub fn remove_for(file_path: &Path) -> io::Result<()> {
+        match fs::remove_file(Self::path_for(file_path)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn update_crash_snapshot(document: &Document) {
+        let snapshot = match document.file_path() {
+            Some(path) if document.is_modified() => Some((path.clone(), document.buffer().clone())),
+            _ => None,
+        };
+        if let Ok(mut guard) = CRASH_SNAPSHOT.lock() {
+            *guard = snapshot;
+        }
+    }
+
+    pub fn flush_crash_snapshot() {
+        if let Ok(guard) = CRASH_SNAPSHOT.try_lock()
+            && let Some((path, buffer)) = guard.as_ref()
+        {
+            let _ = Self::write_buffer(path, buffer);
+        }
+    }
+
+    /// Writes to a temporary file and renames it over the swap, so a crash
+    /// mid-write never leaves a truncated swap behind.
+    fn write_buffer(file_path: &Path, buffer: &RopeBuffer) -> io::Result<()> {
+        let swap_path = Self::path_for(file_path);
+        let mut temp_name = swap_path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = swap_path.with_file_name(temp_name);
+
+        let written = (|| {
+            let mut file = fs::File::create(&temp_path)?;
+            writeln!(file, "{}", SWAP_MAGIC)?;
+            writeln!(file, "{}", file_path.display())?;
+            writeln!(file, "{}", std::process::id())?;
+            for chunk in buffer.chunks() {
+                file.write_all(chunk.as_bytes())?;
+            }
+            file.sync_all()?;
+            fs::rename(&temp_path, &swap_path)
+        })();
+        if written.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        written
+    }
+}
@@ -1,6 +1,7 @@
 pub struct Viewport {
     scroll_offset: usize,
     height: usize,
+    margin: usize,
 }
 
 impl Viewport {
@@ -8,6 +9,7 @@ impl Viewport {
         Self {
             scroll_offset: 0,
             height,
+            margin: 0,
         }
     }
 
@@ -19,15 +21,28 @@ impl Viewport {
         self.height
     }
 
+    pub fn set_scroll_offset(&mut self, offset: usize) {
+        self.scroll_offset = offset;
+    }
+
     pub fn set_height(&mut self, height: usize) {
         self.height = height;
     }
 
+    pub fn scroll_margin(&self) -> usize {
+        self.margin
+    }
+
+    pub fn set_scroll_margin(&mut self, margin: usize) {
+        self.margin = margin;
+    }
+
     pub fn adjust_for_cursor(&mut self, cursor_line: usize) {
-        if cursor_line < self.scroll_offset {
-            self.scroll_offset = cursor_line;
-        } else if cursor_line >= self.scroll_offset + self.height {
-            self.scroll_offset = cursor_line - self.height + 1;
+        let margin = self.margin.min(self.height.saturating_sub(1) / 2);
+        if cursor_line < self.scroll_offset + margin {
+            self.scroll_offset = cursor_line.saturating_sub(margin);
+        } else if cursor_line + margin >= self.scroll_offset + self.height {
+            self.scroll_offset = (cursor_line + margin + 1).saturating_sub(self.height);
         }
     }
 
@@ -21,10 +21,37 @@ impl CommandRegistry {
     pub fn new() -> Self {
         let commands = vec![
             Command::new("open_file", "Open File"),
+            Command::new("find_file", "Find File"),
+            Command::new("open_recent", "Open Recent"),
+            Command::new("next_file", "Next File"),
+            Command::new("previous_file", "Previous File"),
+            Command::new("search", "Search"),
+            Command::new("goto_line", "Go to Line"),
+            Command::new("toggle_explorer", "Toggle File Explorer"),
+            Command::new("focus_explorer", "Focus File Explorer"),
+            Command::new("undo", "Undo"),
+            Command::new("redo", "Redo"),
             Command::new("save", "Save"),
             Command::new("save_as", "Save As"),
             Command::new("new_file", "New File"),
             Command::new("close_file", "Close File"),
+            Command::new("restore_session", "Restore Session"),
+            Command::new("toggle_read_only", "Toggle Read-Only"),
+            Command::new("list_backups", "List Backups"),
+            Command::new("cycle_backup_policy", "Cycle Backup Policy"),
+            Command::new("toggle_auto_save", "Toggle Auto-Save"),
+            Command::new("toggle_line_comment", "Toggle Line Comment"),
+            Command::new("toggle_block_comment", "Toggle Block Comment"),
+            Command::new("jump_to_bracket", "Jump to Matching Bracket"),
+            Command::new("select_inside_brackets", "Select Inside Brackets"),
+            Command::new("select_around_brackets", "Select Around Brackets"),
+            Command::new("expand_selection", "Expand Selection"),
+            Command::new("shrink_selection", "Shrink Selection"),
+            Command::new("goto_parent_node", "Go to Parent Node"),
+            Command::new("goto_next_sibling", "Go to Next Sibling"),
+            Command::new("goto_previous_sibling", "Go to Previous Sibling"),
+            Command::new("select_theme", "Select Theme"),
+            Command::new("reload_config", "Reload Config"),
         ];
 
         Self { commands }
@@ -0,0 +1,33 @@
+/// Pairs whose closer is typed along with the opener, written as
+/// consecutive opener/closer chars: `()[]""`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AutoPairs(Vec<(char, char)>);
+
+impl AutoPairs {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let chars: Vec<char> = spec.chars().collect();
+        if !chars.len().is_multiple_of(2) {
+            return Err("must list opener/closer pairs".to_string());
+        }
+        let pairs: Vec<(char, char)> = chars.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+        for (i, (open, _)) in pairs.iter().enumerate() {
+            if pairs[..i].iter().any(|(other, _)| other == open) {
+                return Err(format!("lists '{}' more than once", open));
+            }
+        }
+        Ok(Self(pairs))
+    }
+
+    pub fn without(mut self, openers: &str) -> Self {
+        self.0.retain(|(open, _)| !openers.contains(*open));
+        self
+    }
+
+    pub fn closer(&self, open: char) -> Option<char> {
+        self.0.iter().find(|pair| pair.0 == open).map(|pair| pair.1)
+    }
+
+    pub fn is_closer(&self, ch: char) -> bool {
+        self.0.iter().any(|pair| pair.1 == ch)
+    }
+}
@@ -0,0 +1,167 @@
+use crate::core::buffer::RopeBuffer;
+use crate::core::cursor::CursorPosition;
+use std::ops::Range;
+
+/// How one line's columns moved: columns from `column` on shift by `delta`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineShift {
+    pub line: usize,
+    pub column: usize,
+    pub delta: isize,
+}
+
+impl LineShift {
+    pub fn apply(&self, position: &mut CursorPosition) {
+        if position.line == self.line && position.column >= self.column {
+            position.column = position
+                .column
+                .saturating_add_signed(self.delta)
+                .max(self.column);
+        }
+    }
+}
+
+pub struct CommentFeature;
+
+impl CommentFeature {
+    /// Uncomments when every non-blank line is already commented. The change
+    /// is made as a single edit.
+    pub fn toggle_line(buffer: &mut RopeBuffer, lines: Range<usize>, marker: &str) -> Vec<LineShift> {
+        let texts: Vec<Vec<char>> = lines
+            .clone()
+            .map(|line| match buffer.line(line) {
+                Some(text) => text.chars().take(buffer.line_len(line)).collect(),
+                None => Vec::new(),
+            })
+            .collect();
+        let indent = |text: &[char]| text.iter().take_while(|c| **c == ' ' || **c == '\t').count();
+        let blank = |text: &[char]| indent(text) == text.len();
+        // A range of only blank lines is commented anyway.
+        let all_blank = texts.iter().all(|text| blank(text));
+        let targets = |text: &[char]| all_blank || !blank(text);
+        let marker_chars: Vec<char> = marker.chars().collect();
+        let commented = !all_blank
+            && texts
+                .iter()
+                .filter(|text| targets(text))
+                .all(|text| text[indent(text)..].starts_with(&marker_chars));
+        let column = texts.iter().filter(|text| targets(text)).map(|text| indent(text)).min().unwrap_or(0);
+
+        let mut shifts = Vec::new();
+        let mut replacement = String::new();
+        for (offset, text) in texts.iter().enumerate() {
+            let line = lines.start + offset;
+            if offset > 0 {
+                // Keep the line's own ending, so CRLF files stay CRLF.
+                let ending = buffer.line_to_char(line - 1) + buffer.line_len(line - 1);
+                replacement.push_str(&buffer.get_slice(ending, buffer.line_to_char(line)));
+            }
+            let mut text = text.clone();
+            if targets(&text) {
+                if commented {
+                    let at = indent(&text);
+                    let mut len = marker_chars.len();
+                    if text.get(at + len) == Some(&' ') {
+                        len += 1;
+                    }
+                    text.drain(at..at + len);
+                    shifts.push(LineShift { line, column: at, delta: -(len as isize) });
+                } else {
+                    text.splice(column..column, marker_chars.iter().copied().chain([' ']));
+                    shifts.push(LineShift { line, column, delta: marker_chars.len() as isize + 1 });
+                }
+            }
+            replacement.extend(text);
+        }
+
+        let start = buffer.line_to_char(lines.start);
+        let last = lines.end - 1;
+        let end = buffer.line_to_char(last) + buffer.line_len(last);
+        buffer.replace(start..end, &replacement);
+        shifts
+    }
+
+    /// Returns the range of the result and how far the commented text moved.
+    pub fn toggle_block(
+        buffer: &mut RopeBuffer,
+        range: Range<usize>,
+        (open, close): (&str, &str),
+    ) -> (Range<usize>, isize) {
+        let text = buffer.get_slice(range.start, range.end);
+        let body = text.trim();
+        let leading = &text[..text.len() - text.trim_start().len()];
+        let trailing = &text[text.trim_end().len()..];
+        let (replacement, moved) = match body
+            .strip_prefix(open)
+            .and_then(|rest| rest.strip_suffix(close))
+        {
+            Some(inner) => {
+                let unpadded = inner.strip_prefix(' ').unwrap_or(inner);
+                let removed = open.chars().count() + inner.len() - unpadded.len();
+                let unpadded = unpadded.strip_suffix(' ').unwrap_or(unpadded);
+                (format!("{}{}{}", leading, unpadded, trailing), -(removed as isize))
+            }
+            None => (
+                format!("{}{} {} {}{}", leading, open, body, close, trailing),
+                open.chars().count() as isize + 1,
+            ),
+        };
+        buffer.replace(range.clone(), &replacement);
+        (range.start..range.start + replacement.chars().count(), moved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toggle(text: &str, lines: Range<usize>) -> String {
+        let mut buffer = RopeBuffer::from_string(text);
+        CommentFeature::toggle_line(&mut buffer, lines, "//");
+        buffer.get_slice(0, buffer.len_chars())
+    }
+
+    #[test]
+    fn comments_at_the_minimum_indent_and_skips_blank_lines() {
+        assert_eq!(toggle("  a\n    b\n\n  c\n", 0..4), "  // a\n  //   b\n\n  // c\n");
+        assert_eq!(toggle("a\nb", 1..2), "a\n// b");
+    }
+
+    #[test]
+    fn uncomments_only_when_every_line_is_commented() {
+        assert_eq!(toggle("  // a\n  //   b\n\n  //c\n", 0..4), "  a\n    b\n\n  c\n");
+        assert_eq!(toggle("// a\nb\n", 0..2), "// // a\n// b\n");
+    }
+
+    #[test]
+    fn all_blank_range_is_commented() {
+        assert_eq!(toggle("\n  \n", 0..2), "// \n//   \n");
+    }
+
+    #[test]
+    fn keeps_crlf_line_endings() {
+        assert_eq!(toggle("a\r\n  b\r\nc", 0..3), "// a\r\n//   b\r\n// c");
+        assert_eq!(toggle("// a\r\n// b\r\n", 0..2), "a\r\nb\r\n");
+    }
+
+    #[test]
+    fn shifts_describe_the_moved_columns() {
+        let mut buffer = RopeBuffer::from_string("  a\n  // b");
+        let shifts = CommentFeature::toggle_line(&mut buffer, 1..2, "//");
+        assert_eq!(shifts, vec![LineShift { line: 1, column: 2, delta: -3 }]);
+        let mut cursor = CursorPosition::at(1, 6);
+        shifts[0].apply(&mut cursor);
+        assert_eq!(cursor, CursorPosition::at(1, 3));
+    }
+
+    #[test]
+    fn block_toggle_round_trips() {
+        let mut buffer = RopeBuffer::from_string("x = 1 + 2;");
+        let (range, moved) = CommentFeature::toggle_block(&mut buffer, 4..9, ("/*", "*/"));
+        assert_eq!(buffer.get_slice(0, buffer.len_chars()), "x = /* 1 + 2 */;");
+        assert_eq!((range.clone(), moved), (4..15, 3));
+        let (range, moved) = CommentFeature::toggle_block(&mut buffer, range, ("/*", "*/"));
+        assert_eq!(buffer.get_slice(0, buffer.len_chars()), "x = 1 + 2;");
+        assert_eq!((range, moved), (4..9, -3));
+    }
+}
@@ -1,5 +1,6 @@
+use super::AutoPairs;
 use crate::core::buffer::RopeBuffer;
-use crate::core::cursor::CursorPosition;
+use crate::core::cursor::{CursorPosition, Selection};
 
 pub struct DeleteFeature;
 
@@ -7,10 +8,15 @@ impl DeleteFeature {
     pub fn backspace(
         buffer: &mut RopeBuffer,
         cursor: &mut CursorPosition,
+        pairs: &AutoPairs,
     ) {
         if cursor.column > 0 {
             let idx = Self::cursor_to_char_idx(buffer, cursor);
-            buffer.remove(idx - 1..idx);
+            let mut chars = buffer.chars_at(idx);
+            let next = chars.next();
+            chars.prev();
+            let empty_pair = chars.prev().and_then(|open| pairs.closer(open)).is_some_and(|close| next == Some(close));
+            buffer.remove(idx - 1..if empty_pair { idx + 1 } else { idx });
             cursor.column -= 1;
         } else if cursor.line > 0 {
             let prev_line_len = buffer.line_len(cursor.line - 1);
@@ -31,8 +37,48 @@ impl DeleteFeature {
         }
     }
 
+    pub fn delete_selection(
+        buffer: &mut RopeBuffer,
+        cursor: &mut CursorPosition,
+        selection: &Selection,
+    ) {
+        buffer.remove(selection.char_range(buffer));
+        *cursor = selection.start();
+    }
+
     fn cursor_to_char_idx(buffer: &RopeBuffer, cursor: &CursorPosition) -> usize {
         let line_start = buffer.line_to_char(cursor.line);
         line_start + cursor.column
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backspace(text: &str, line: usize, column: usize) -> (String, usize, usize) {
+        let mut buffer = RopeBuffer::from_string(text);
+        let mut cursor = CursorPosition::at(line, column);
+        let pairs = AutoPairs::parse("()[]\"\"").unwrap();
+        DeleteFeature::backspace(&mut buffer, &mut cursor, &pairs);
+        (buffer.get_slice(0, buffer.len_chars()), cursor.line, cursor.column)
+    }
+
+    #[test]
+    fn backspace_in_an_empty_pair_deletes_both_halves() {
+        assert_eq!(backspace("f()", 0, 2), ("f".to_string(), 0, 1));
+        assert_eq!(backspace("\"\"", 0, 1), (String::new(), 0, 0));
+    }
+
+    #[test]
+    fn backspace_elsewhere_deletes_one_char() {
+        assert_eq!(backspace("(a)", 0, 2), ("()".to_string(), 0, 1));
+        assert_eq!(backspace("(]", 0, 1), ("]".to_string(), 0, 0));
+        assert_eq!(backspace("{}", 0, 1), ("}".to_string(), 0, 0));
+    }
+
+    #[test]
+    fn backspace_at_line_start_joins_lines() {
+        assert_eq!(backspace("ab\n()", 1, 0), ("ab()".to_string(), 0, 2));
+        assert_eq!(backspace("ab", 0, 0), ("ab".to_string(), 0, 0));
+    }
+}
@@ -1,5 +1,8 @@
 use crate::core::buffer::RopeBuffer;
-use crate::core::cursor::CursorPosition;
+use super::AutoPairs;
+use crate::core::cursor::{CursorPosition, Selection};
+use crate::core::document::Indentation;
+use crate::features::navigation::BracketPairs;
 
 pub struct InsertFeature;
 
@@ -14,27 +17,261 @@ impl InsertFeature {
         cursor.column += 1;
     }
 
-    pub fn insert_newline(
+    /// An opener also inserts its closer before whitespace or a closer; a
+    /// closer already at the cursor is typed over.
+    pub fn type_char(
         buffer: &mut RopeBuffer,
         cursor: &mut CursorPosition,
+        ch: char,
+        pairs: &AutoPairs,
     ) {
         let idx = Self::cursor_to_char_idx(buffer, cursor);
-        buffer.insert_char(idx, '\n');
+        let mut chars = buffer.chars_at(idx);
+        let next = chars.next();
+        chars.prev();
+        let prev = chars.prev();
+        if pairs.is_closer(ch) && next == Some(ch) {
+            cursor.column += 1;
+            return;
+        }
+        if let Some(close) = pairs.closer(ch) {
+            let before_space = next.is_none_or(|c| c.is_whitespace() || pairs.is_closer(c));
+            // A quote right after a word is an apostrophe or a closing quote.
+            let after_word = close == ch && prev.is_some_and(|c| c.is_alphanumeric() || c == '_');
+            if before_space && !after_word {
+                buffer.insert(idx, &format!("{}{}", ch, close));
+                cursor.column += 1;
+                return;
+            }
+        }
+        Self::insert_char(buffer, cursor, ch);
+    }
+
+    pub fn wrap_selection(
+        buffer: &mut RopeBuffer,
+        cursor: &mut CursorPosition,
+        selection: &Selection,
+        open: char,
+        close: char,
+    ) -> Selection {
+        let range = selection.char_range(buffer);
+        buffer.insert_char(range.end, close);
+        buffer.insert_char(range.start, open);
+        let inner = Selection::from_char_range(buffer, range.start + 1..range.end + 1);
+        *cursor = inner.head;
+        inner
+    }
+
+    /// Indents one level deeper after an opener or `indent_after`; between a
+    /// bracket pair the closer gets a line of its own.
+    pub fn insert_newline(
+        buffer: &mut RopeBuffer,
+        cursor: &mut CursorPosition,
+        indentation: &Indentation,
+        pairs: &BracketPairs,
+        indent_after: &[&str],
+    ) {
+        let chars: Vec<char> = buffer
+            .line(cursor.line)
+            .map(|line| line.chars().take(buffer.line_len(cursor.line)).collect())
+            .unwrap_or_default();
+        let column = cursor.column.min(chars.len());
+        let before = &chars[..column];
+        let indent: String = before.iter().take_while(|c| **c == ' ' || **c == '\t').collect();
+        let head_len = before.iter().rposition(|c| !c.is_whitespace()).map_or(0, |i| i + 1);
+        let head: String = before[..head_len].iter().collect();
+        // Text moved to the new line loses its leading whitespace, unless
+        // the cursor was inside the indentation.
+        let skipped = if head.is_empty() {
+            0
+        } else {
+            chars[column..].iter().take_while(|c| **c == ' ' || **c == '\t').count()
+        };
+
+        let closer = head.chars().last().and_then(|c| pairs.closer(c));
+        let deeper = closer.is_some()
+            || indent_after.iter().any(|token| Self::ends_with_token(&head, token));
+        let inner = if deeper {
+            indent.clone() + &Self::indent_unit(indentation)
+        } else {
+            indent.clone()
+        };
+        let mut text = format!("\n{}", inner);
+        if closer.is_some() && closer == chars.get(column + skipped).copied() {
+            text.push('\n');
+            text.push_str(&indent);
+        }
+
+        let line_start = buffer.line_to_char(cursor.line);
+        buffer.remove(line_start + head_len..line_start + column + skipped);
+        buffer.insert(line_start + head_len, &text);
         cursor.line += 1;
-        cursor.column = 0;
+        cursor.column = inner.chars().count();
+    }
+
+    pub fn insert_text(
+        buffer: &mut RopeBuffer,
+        cursor: &mut CursorPosition,
+        text: &str,
+    ) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let idx = Self::cursor_to_char_idx(buffer, cursor);
+        buffer.insert(idx, &text);
+        match text.rsplit_once('\n') {
+            Some((head, tail)) => {
+                cursor.line += head.matches('\n').count() + 1;
+                cursor.column = tail.chars().count();
+            }
+            None => cursor.column += text.chars().count(),
+        }
     }
 
     pub fn insert_tab(
         buffer: &mut RopeBuffer,
         cursor: &mut CursorPosition,
+        indentation: &Indentation,
     ) {
+        if indentation.use_tabs {
+            Self::insert_char(buffer, cursor, '\t');
+            return;
+        }
+        let width = indentation.size.max(1);
+        let count = width - cursor.column % width;
         let idx = Self::cursor_to_char_idx(buffer, cursor);
-        buffer.insert(idx, "    ");
-        cursor.column += 4;
+        buffer.insert(idx, &" ".repeat(count));
+        cursor.column += count;
+    }
+
+    fn indent_unit(indentation: &Indentation) -> String {
+        if indentation.use_tabs {
+            "\t".to_string()
+        } else {
+            " ".repeat(indentation.size.max(1))
+        }
+    }
+
+    fn ends_with_token(line: &str, token: &str) -> bool {
+        let Some(rest) = line.strip_suffix(token) else {
+            return false;
+        };
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        !token.starts_with(is_word) || !rest.ends_with(is_word)
     }
 
     fn cursor_to_char_idx(buffer: &RopeBuffer, cursor: &CursorPosition) -> usize {
         let line_start = buffer.line_to_char(cursor.line);
         line_start + cursor.column
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Splits `|` out of `text` as the cursor position.
+    fn buffer_with_cursor(text: &str) -> (RopeBuffer, CursorPosition) {
+        let idx = text.chars().position(|c| c == '|').unwrap();
+        let buffer = RopeBuffer::from_string(&text.replacen('|', "", 1));
+        let cursor = CursorPosition::from_char_idx(&buffer, idx);
+        (buffer, cursor)
+    }
+
+    fn with_cursor(buffer: &RopeBuffer, cursor: &CursorPosition) -> String {
+        let mut text = buffer.get_slice(0, buffer.len_chars());
+        let idx = text
+            .char_indices()
+            .nth(buffer.line_to_char(cursor.line) + cursor.column)
+            .map_or(text.len(), |(i, _)| i);
+        text.insert(idx, '|');
+        text
+    }
+
+    fn newline(text: &str, use_tabs: bool) -> String {
+        let (mut buffer, mut cursor) = buffer_with_cursor(text);
+        let indentation = Indentation {
+            size: 4,
+            tab_width: 4,
+            use_tabs,
+        };
+        let pairs = BracketPairs::default();
+        InsertFeature::insert_newline(&mut buffer, &mut cursor, &indentation, &pairs, &[":", "do"]);
+        with_cursor(&buffer, &cursor)
+    }
+
+    #[test]
+    fn newline_keeps_the_current_indent() {
+        assert_eq!(newline("  foo|", false), "  foo\n  |");
+        assert_eq!(newline("\tfoo|\nbar", true), "\tfoo\n\t|\nbar");
+        assert_eq!(newline("foo|", false), "foo\n|");
+    }
+
+    #[test]
+    fn newline_indents_after_an_opener_or_token() {
+        assert_eq!(newline("  if x {|", false), "  if x {\n      |");
+        assert_eq!(newline("if x {|", true), "if x {\n\t|");
+        assert_eq!(newline("def f():|", false), "def f():\n    |");
+        assert_eq!(newline("each do|", false), "each do\n    |");
+        assert_eq!(newline("undo|", false), "undo\n|");
+    }
+
+    #[test]
+    fn newline_between_a_pair_gives_the_closer_its_own_line() {
+        assert_eq!(newline("  f({|})", false), "  f({\n      |\n  })");
+        assert_eq!(newline("[|]", true), "[\n\t|\n]");
+        assert_eq!(newline("{|)", false), "{\n    |)");
+    }
+
+    #[test]
+    fn newline_drops_whitespace_around_the_split() {
+        assert_eq!(newline("  a = 1;  |  b", false), "  a = 1;\n  |b");
+        assert_eq!(newline("  {  |  }", false), "  {\n      |\n  }");
+    }
+
+    #[test]
+    fn newline_inside_the_indentation_moves_it_down() {
+        assert_eq!(newline("  |  foo", false), "\n  |  foo");
+        assert_eq!(newline("|foo", false), "\n|foo");
+    }
+
+    fn type_chars(text: &str, typed: &str) -> String {
+        let (mut buffer, mut cursor) = buffer_with_cursor(text);
+        let pairs = AutoPairs::parse("()[]{}\"\"''").unwrap();
+        for ch in typed.chars() {
+            InsertFeature::type_char(&mut buffer, &mut cursor, ch, &pairs);
+        }
+        with_cursor(&buffer, &cursor)
+    }
+
+    #[test]
+    fn opener_inserts_its_closer_before_whitespace_or_a_closer() {
+        assert_eq!(type_chars("|", "("), "(|)");
+        assert_eq!(type_chars("f| x", "("), "f(|) x");
+        assert_eq!(type_chars("(|)", "["), "([|])");
+        assert_eq!(type_chars("|x", "("), "(|x");
+    }
+
+    #[test]
+    fn typed_closer_steps_over_the_one_at_the_cursor() {
+        assert_eq!(type_chars("|", "(a)"), "(a)|");
+        assert_eq!(type_chars("|", "{[]}"), "{[]}|");
+        assert_eq!(type_chars("a|", ")"), "a)|");
+    }
+
+    #[test]
+    fn quotes_pair_except_after_a_word() {
+        assert_eq!(type_chars("x = |", "\""), "x = \"|\"");
+        assert_eq!(type_chars("x = |", "\"ab\""), "x = \"ab\"|");
+        assert_eq!(type_chars("don|", "'t"), "don't|");
+        assert_eq!(type_chars("f_|", "'"), "f_'|");
+    }
+
+    #[test]
+    fn wrap_selection_surrounds_and_keeps_the_text_selected() {
+        let mut buffer = RopeBuffer::from_string("a bc d");
+        let mut cursor = CursorPosition::at(0, 4);
+        let selection = Selection::new(CursorPosition::at(0, 2), cursor);
+        let inner = InsertFeature::wrap_selection(&mut buffer, &mut cursor, &selection, '[', ']');
+        assert_eq!(buffer.get_slice(0, buffer.len_chars()), "a [bc] d");
+        assert_eq!(inner.char_range(&buffer), 3..5);
+        assert_eq!(cursor, CursorPosition::at(0, 3));
+    }
+}
@@ -1,7 +1,11 @@
 mod insert;
 mod delete;
 mod clipboard;
+mod auto_pairs;
+mod comment;
 
 pub use insert::InsertFeature;
 pub use delete::DeleteFeature;
-pub use clipboard::ClipboardFeature;
\ No newline at end of file
+pub use clipboard::ClipboardFeature;
+pub use auto_pairs::AutoPairs;
+pub use comment::{CommentFeature, LineShift};
@@ -0,0 +1,280 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+    tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    AnyChar,
+    Star,
+    GlobStar,
+    Class { negated: bool, ranges: Vec<(char, char)> },
+    Alternatives(Vec<Vec<Token>>),
+    Numbers(i64, i64),
+}
+
+impl Glob {
+    /// Patterns without a `/` match the file name at any depth below `dir`.
+    pub fn new(dir: &str, pattern: &str) -> Self {
+        let dir = dir.trim_end_matches('/');
+        let mut tokens: Vec<Token> = dir.chars().map(Token::Literal).collect();
+        if pattern.contains('/') {
+            tokens.push(Token::Literal('/'));
+        } else {
+            tokens.push(any_depth());
+        }
+        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+        let chars: Vec<char> = pattern.chars().collect();
+        tokens.extend(parse(&chars, &mut 0, false));
+        Self { tokens }
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        let text: Vec<char> = path.chars().collect();
+        match_tokens(&self.tokens, &text)
+    }
+}
+
+/// `/` or `/**/`, so `a/**/b` also matches `a/b`.
+fn any_depth() -> Token {
+    Token::Alternatives(vec![
+        vec![Token::Literal('/')],
+        vec![Token::Literal('/'), Token::GlobStar, Token::Literal('/')],
+    ])
+}
+
+fn parse(chars: &[char], pos: &mut usize, in_braces: bool) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    while *pos < chars.len() {
+        let ch = chars[*pos];
+        if in_braces && (ch == ',' || ch == '}') {
+            break;
+        }
+        *pos += 1;
+        match ch {
+            '\\' if *pos < chars.len() => {
+                tokens.push(Token::Literal(chars[*pos]));
+                *pos += 1;
+            }
+            '?' => tokens.push(Token::AnyChar),
+            '*' if chars.get(*pos) == Some(&'*') => {
+                *pos += 1;
+                if tokens.last() == Some(&Token::Literal('/')) && chars.get(*pos) == Some(&'/') {
+                    *pos += 1;
+                    tokens.pop();
+                    tokens.push(any_depth());
+                } else {
+                    tokens.push(Token::GlobStar);
+                }
+            }
+            '*' => tokens.push(Token::Star),
+            '[' => match parse_class(chars, *pos) {
+                Some((token, end)) => {
+                    tokens.push(token);
+                    *pos = end;
+                }
+                None => tokens.push(Token::Literal('[')),
+            },
+            '{' => match parse_braces(chars, *pos) {
+                Some((token, end)) => {
+                    tokens.push(token);
+                    *pos = end;
+                }
+                None => tokens.push(Token::Literal('{')),
+            },
+            _ => tokens.push(Token::Literal(ch)),
+        }
+    }
+    tokens
+}
+
+fn parse_class(chars: &[char], start: usize) -> Option<(Token, usize)> {
+    let mut pos = start;
+    let negated = matches!(chars.get(pos), Some('!') | Some('^'));
+    if negated {
+        pos += 1;
+    }
+    let mut ranges = Vec::new();
+    let mut first = true;
+    loop {
+        let ch = *chars.get(pos)?;
+        if ch == ']' && !first {
+            return Some((Token::Class { negated, ranges }, pos + 1));
+        }
+        if ch == '/' {
+            return None;
+        }
+        first = false;
+        let ch = if ch == '\\' {
+            pos += 1;
+            *chars.get(pos)?
+        } else {
+            ch
+        };
+        if chars.get(pos + 1) == Some(&'-')
+            && let Some(&end) = chars.get(pos + 2)
+            && end != ']'
+        {
+            ranges.push((ch, end));
+            pos += 3;
+        } else {
+            ranges.push((ch, ch));
+            pos += 1;
+        }
+    }
+}
+
+/// Parses `{...}` starting after `{`. A brace group without a comma or a
+/// numeric range is taken literally.
+fn parse_braces(chars: &[char], start: usize) -> Option<(Token, usize)> {
+    let close = find_close(chars, start)?;
+    let body: String = chars[start..close].iter().collect();
+    if let Some((low, high)) = body.split_once("..")
+        && let (Ok(low), Ok(high)) = (low.parse::<i64>(), high.parse::<i64>())
+    {
+        return Some((Token::Numbers(low.min(high), low.max(high)), close + 1));
+    }
+
+    let mut alternatives = Vec::new();
+    let mut pos = start;
+    loop {
+        alternatives.push(parse(chars, &mut pos, true));
+        match chars.get(pos) {
+            Some(',') => pos += 1,
+            _ => break,
+        }
+    }
+    if alternatives.len() < 2 {
+        return None;
+    }
+    Some((Token::Alternatives(alternatives), close + 1))
+}
+
+fn find_close(chars: &[char], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut pos = start;
+    while pos < chars.len() {
+        match chars[pos] {
+            '\\' => pos += 1,
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(pos),
+            '}' => depth -= 1,
+            _ => {}
+        }
+        pos += 1;
+    }
+    None
+}
+
+fn match_tokens(tokens: &[Token], text: &[char]) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return text.is_empty();
+    };
+    match token {
+        Token::Literal(ch) => text.first() == Some(ch) && match_tokens(rest, &text[1..]),
+        Token::AnyChar => {
+            text.first().is_some_and(|&c| c != '/') && match_tokens(rest, &text[1..])
+        }
+        Token::Star => {
+            let limit = text.iter().position(|&c| c == '/').unwrap_or(text.len());
+            (0..=limit).any(|n| match_tokens(rest, &text[n..]))
+        }
+        Token::GlobStar => (0..=text.len()).any(|n| match_tokens(rest, &text[n..])),
+        Token::Class { negated, ranges } => match text.first() {
+            Some(&c) if c != '/' => {
+                let hit = ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi);
+                hit != *negated && match_tokens(rest, &text[1..])
+            }
+            _ => false,
+        },
+        Token::Alternatives(alternatives) => alternatives.iter().any(|alternative| {
+            let mut sequence = alternative.clone();
+            sequence.extend_from_slice(rest);
+            match_tokens(&sequence, text)
+        }),
+        Token::Numbers(low, high) => {
+            let sign = usize::from(text.first() == Some(&'-'));
+            let digits = text[sign..].iter().take_while(|c| c.is_ascii_digit()).count();
+            (1..=digits).any(|n| {
+                let number: String = text[..sign + n].iter().collect();
+                number.parse::<i64>().is_ok_and(|v| *low <= v && v <= *high)
+                    && match_tokens(rest, &text[sign + n..])
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(pattern: &str, path: &str) -> bool {
+        Glob::new("/p", pattern).matches(path)
+    }
+
+    #[test]
+    fn pattern_without_slash_matches_file_name_at_any_depth() {
+        assert!(matches("*.rs", "/p/main.rs"));
+        assert!(matches("*.rs", "/p/src/deep/main.rs"));
+        assert!(!matches("*.rs", "/p/main.rsx"));
+        assert!(!matches("*.rs", "/other/main.rs"));
+    }
+
+    #[test]
+    fn pattern_with_slash_is_anchored_to_the_directory() {
+        assert!(matches("src/*.rs", "/p/src/main.rs"));
+        assert!(!matches("src/*.rs", "/p/src/deep/main.rs"));
+        assert!(!matches("src/*.rs", "/p/lib/src/main.rs"));
+        assert!(matches("/src/*.rs", "/p/src/main.rs"));
+    }
+
+    #[test]
+    fn globstar_crosses_directories_and_star_does_not() {
+        assert!(matches("src/**/*.rs", "/p/src/main.rs"));
+        assert!(matches("src/**/*.rs", "/p/src/a/b/main.rs"));
+        assert!(matches("src/**", "/p/src/a/b.txt"));
+        assert!(!matches("src/*", "/p/src/a/b.txt"));
+        assert!(!matches("a?c", "/p/a/c"));
+    }
+
+    #[test]
+    fn alternatives_nest_and_single_items_stay_literal() {
+        assert!(matches("*.{js,ts}", "/p/x.js"));
+        assert!(matches("*.{js,ts}", "/p/x.ts"));
+        assert!(!matches("*.{js,ts}", "/p/x.rs"));
+        assert!(matches("{a,b{c,d}}.txt", "/p/bd.txt"));
+        assert!(!matches("{a,b{c,d}}.txt", "/p/b.txt"));
+        assert!(matches("{single}.txt", "/p/{single}.txt"));
+        assert!(matches("{,x}y", "/p/y"));
+    }
+
+    #[test]
+    fn classes_support_ranges_and_negation() {
+        assert!(matches("[abc].txt", "/p/b.txt"));
+        assert!(matches("[a-c].txt", "/p/c.txt"));
+        assert!(!matches("[a-c].txt", "/p/d.txt"));
+        assert!(matches("[!x].txt", "/p/y.txt"));
+        assert!(!matches("[!x].txt", "/p/x.txt"));
+        assert!(matches("[^x].txt", "/p/y.txt"));
+        assert!(matches("[]].txt", "/p/].txt"));
+        assert!(matches("[a.txt", "/p/[a.txt"));
+    }
+
+    #[test]
+    fn numeric_ranges_compare_values() {
+        assert!(matches("file{1..10}.txt", "/p/file1.txt"));
+        assert!(matches("file{1..10}.txt", "/p/file10.txt"));
+        assert!(!matches("file{1..10}.txt", "/p/file11.txt"));
+        assert!(!matches("file{1..10}.txt", "/p/file0.txt"));
+        assert!(matches("file{10..1}.txt", "/p/file5.txt"));
+        assert!(matches("v{-2..2}", "/p/v-1"));
+        assert!(!matches("v{-2..2}", "/p/v-3"));
+    }
+
+    #[test]
+    fn escapes_are_literal() {
+        assert!(matches("\\*.txt", "/p/*.txt"));
+        assert!(!matches("\\*.txt", "/p/a.txt"));
+    }
+}
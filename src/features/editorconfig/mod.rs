@@ -0,0 +1,5 @@
+mod glob;
+mod resolver;
+
+pub use glob::Glob;
+pub use resolver::EditorConfig;
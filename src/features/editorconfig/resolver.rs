@@ -0,0 +1,202 @@
+use super::Glob;
+use crate::core::document::{Charset, DocumentSettings, IndentStyle, LineEnding};
+use crate::utils::paths;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+const FILE_NAME: &str = ".editorconfig";
+
+struct Section {
+    glob: Glob,
+    properties: Vec<(String, String)>,
+}
+
+struct ConfigFile {
+    root: bool,
+    sections: Vec<Section>,
+}
+
+pub struct EditorConfig;
+
+impl EditorConfig {
+    /// Resolves the `.editorconfig` properties that apply to `file_path`.
+    /// Unreadable files and unknown values are ignored, as the spec asks.
+    pub fn resolve(file_path: &Path) -> DocumentSettings {
+        let file_path = paths::absolute(file_path);
+        let target = to_slashes(&file_path);
+
+        let mut files = Vec::new();
+        for dir in file_path.ancestors().skip(1) {
+            if let Some(file) = Self::read(dir) {
+                let root = file.root;
+                files.push(file);
+                if root {
+                    break;
+                }
+            }
+        }
+
+        // Closer files and later sections win, so apply from the top down.
+        let mut properties: HashMap<String, String> = HashMap::new();
+        for file in files.iter().rev() {
+            for section in file.sections.iter().filter(|s| s.glob.matches(&target)) {
+                for (key, value) in &section.properties {
+                    properties.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        properties.retain(|_, value| !value.eq_ignore_ascii_case("unset"));
+        settings_from(&properties)
+    }
+
+    fn read(dir: &Path) -> Option<ConfigFile> {
+        let raw = fs::read_to_string(dir.join(FILE_NAME)).ok()?;
+        let dir = to_slashes(dir);
+        let mut file = ConfigFile {
+            root: false,
+            sections: Vec::new(),
+        };
+        for line in raw.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+            if let Some(pattern) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                file.sections.push(Section {
+                    glob: Glob::new(&dir, pattern),
+                    properties: Vec::new(),
+                });
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim().to_string();
+            match file.sections.last_mut() {
+                Some(section) => section.properties.push((key, value)),
+                None if key == "root" => file.root = value.eq_ignore_ascii_case("true"),
+                None => {}
+            }
+        }
+        Some(file)
+    }
+}
+
+fn settings_from(properties: &HashMap<String, String>) -> DocumentSettings {
+    let value = |key: &str| properties.get(key).map(|v| v.to_lowercase());
+    let number = |key: &str| value(key).and_then(|v| v.parse::<usize>().ok()).filter(|&n| n > 0);
+    let flag = |key: &str| value(key).as_deref() == Some("true");
+
+    let indent_style = match value("indent_style").as_deref() {
+        Some("tab") => Some(IndentStyle::Tab),
+        Some("space") => Some(IndentStyle::Space),
+        _ => None,
+    };
+    let tab_width = number("tab_width");
+    // `indent_size = tab` follows the tab width.
+    let indent_size = match value("indent_size").as_deref() {
+        Some("tab") => tab_width,
+        _ => number("indent_size"),
+    };
+    DocumentSettings {
+        indent_style,
+        indent_size,
+        tab_width,
+        end_of_line: match value("end_of_line").as_deref() {
+            Some("lf") => Some(LineEnding::Lf),
+            Some("crlf") => Some(LineEnding::CrLf),
+            Some("cr") => Some(LineEnding::Cr),
+            _ => None,
+        },
+        charset: match value("charset").as_deref() {
+            Some("utf-8") => Some(Charset::Utf8),
+            Some("utf-8-bom") => Some(Charset::Utf8Bom),
+            Some("latin1") => Some(Charset::Latin1),
+            Some("utf-16le") => Some(Charset::Utf16Le),
+            Some("utf-16be") => Some(Charset::Utf16Be),
+            _ => None,
+        },
+        trim_trailing_whitespace: flag("trim_trailing_whitespace"),
+        insert_final_newline: flag("insert_final_newline"),
+        max_line_length: number("max_line_length"),
+    }
+}
+
+fn to_slashes(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_tree(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("kappa-ec-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn later_sections_and_closer_files_win() {
+        let dir = temp_tree("precedence");
+        fs::write(
+            dir.join(FILE_NAME),
+            "root = true\n\n[*]\nindent_style = space\nindent_size = 4\n\n[*.rs]\nindent_size = 2\ntab_width = 8\n",
+        )
+        .unwrap();
+        fs::write(dir.join("sub").join(FILE_NAME), "[*.rs]\nindent_style = tab\n").unwrap();
+
+        let top = EditorConfig::resolve(&dir.join("a.rs"));
+        assert_eq!(top.indent_style, Some(IndentStyle::Space));
+        assert_eq!(top.indent_size, Some(2));
+
+        let nested = EditorConfig::resolve(&dir.join("sub").join("a.rs"));
+        assert_eq!(nested.indent_style, Some(IndentStyle::Tab));
+        assert_eq!(nested.indent_size, Some(2));
+        assert_eq!(nested.tab_width, Some(8));
+
+        let other = EditorConfig::resolve(&dir.join("sub").join("a.txt"));
+        assert_eq!(other.indent_size, Some(4));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn root_stops_the_search_and_unset_clears_a_value() {
+        let dir = temp_tree("root");
+        fs::write(
+            dir.join(FILE_NAME),
+            "root = true\n[*]\ninsert_final_newline = true\nend_of_line = crlf\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("sub").join(FILE_NAME),
+            "root = true\n[*]\ntrim_trailing_whitespace = true\n",
+        )
+        .unwrap();
+        let nested = EditorConfig::resolve(&dir.join("sub").join("a.txt"));
+        assert!(nested.trim_trailing_whitespace);
+        assert!(!nested.insert_final_newline);
+
+        fs::write(dir.join("sub").join(FILE_NAME), "[*]\nend_of_line = unset\n").unwrap();
+        let nested = EditorConfig::resolve(&dir.join("sub").join("a.txt"));
+        assert!(nested.insert_final_newline);
+        assert_eq!(nested.end_of_line, None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn indent_size_tab_follows_tab_width() {
+        let properties = HashMap::from([
+            ("indent_size".to_string(), "tab".to_string()),
+            ("tab_width".to_string(), "3".to_string()),
+            ("charset".to_string(), "UTF-16LE".to_string()),
+        ]);
+        let settings = settings_from(&properties);
+        assert_eq!(settings.indent_size, Some(3));
+        assert_eq!(settings.charset, Some(Charset::Utf16Le));
+    }
+}
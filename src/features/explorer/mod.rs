@@ -0,0 +1,7 @@
+mod operations;
+mod prompt;
+mod tree;
+
+pub use operations::ExplorerOperations;
+pub use prompt::ExplorerPrompt;
+pub use tree::{FileTree, ScannedTree, TreeEntry, TreeScan};
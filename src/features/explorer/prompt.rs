@@ -0,0 +1,57 @@
+use crate::utils::paths;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplorerPrompt {
+    NewFile(PathBuf),
+    NewDirectory(PathBuf),
+    Rename(PathBuf),
+    Move(PathBuf),
+}
+
+impl ExplorerPrompt {
+    pub fn title(&self) -> &'static str {
+        match self {
+            ExplorerPrompt::NewFile(_) => "New File",
+            ExplorerPrompt::NewDirectory(_) => "New Directory",
+            ExplorerPrompt::Rename(_) => "Rename",
+            ExplorerPrompt::Move(_) => "Move To",
+        }
+    }
+
+    pub fn initial_input(&self, root: &Path) -> String {
+        match self {
+            ExplorerPrompt::NewFile(_) | ExplorerPrompt::NewDirectory(_) => String::new(),
+            ExplorerPrompt::Rename(path) => path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            ExplorerPrompt::Move(path) => path
+                .strip_prefix(root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .into_owned(),
+        }
+    }
+
+    pub fn completes_paths(&self) -> bool {
+        matches!(self, ExplorerPrompt::Move(_))
+    }
+
+    pub fn target(&self, input: &str) -> PathBuf {
+        match self {
+            ExplorerPrompt::NewFile(dir) | ExplorerPrompt::NewDirectory(dir) => dir.join(input),
+            ExplorerPrompt::Rename(path) => path
+                .parent()
+                .map(|p| p.join(input))
+                .unwrap_or_else(|| PathBuf::from(input)),
+            ExplorerPrompt::Move(path) => {
+                let target = paths::absolute(&paths::expand_tilde(input));
+                match path.file_name() {
+                    Some(name) if target.is_dir() => target.join(name),
+                    _ => target,
+                }
+            }
+        }
+    }
+}
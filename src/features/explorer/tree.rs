@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEntry {
+    pub path: PathBuf,
+    pub name: String,
+    pub depth: usize,
+    pub is_dir: bool,
+    pub expanded: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeScan {
+    root: PathBuf,
+    expanded: HashSet<PathBuf>,
+    show_hidden: bool,
+}
+
+pub struct ScannedTree {
+    scan: TreeScan,
+    entries: Vec<TreeEntry>,
+    expanded: HashSet<PathBuf>,
+}
+
+#[derive(Default)]
+pub struct FileTree {
+    root: PathBuf,
+    entries: Vec<TreeEntry>,
+    expanded: HashSet<PathBuf>,
+    selected: usize,
+    show_hidden: bool,
+    scanning: bool,
+}
+
+impl FileTree {
+    pub fn set_root(&mut self, root: PathBuf) {
+        if self.root != root {
+            self.root = root;
+            self.expanded.clear();
+            self.selected = 0;
+        }
+        self.refresh();
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn entries(&self) -> &[TreeEntry] {
+        &self.entries
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_entry(&self) -> Option<&TreeEntry> {
+        self.entries.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.entries.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_path(&mut self, path: &Path) -> bool {
+        match self.entries.iter().position(|e| e.path == path) {
+            Some(index) => {
+                self.selected = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn toggle_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        self.refresh();
+    }
+
+    pub fn expand(&mut self) {
+        if let Some(entry) = self.selected_entry()
+            && entry.is_dir
+            && !entry.expanded
+        {
+            let path = entry.path.clone();
+            self.expanded.insert(path);
+            self.refresh();
+        }
+    }
+
+    pub fn collapse(&mut self) {
+        let Some(entry) = self.selected_entry().cloned() else {
+            return;
+        };
+        if entry.is_dir && entry.expanded {
+            self.expanded.remove(&entry.path);
+            self.refresh();
+        } else if let Some(parent) = entry.path.parent()
+            && parent != self.root
+        {
+            let parent = parent.to_path_buf();
+            self.select_path(&parent);
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        match self.selected_entry() {
+            Some(entry) if entry.expanded => self.collapse(),
+            Some(_) => self.expand(),
+            None => {}
+        }
+    }
+
+    pub fn reveal(&mut self, path: &Path) {
+        for ancestor in path.ancestors().skip(1) {
+            if !ancestor.starts_with(&self.root) || ancestor == self.root {
+                break;
+            }
+            self.expanded.insert(ancestor.to_path_buf());
+        }
+        self.refresh();
+        self.select_path(path);
+    }
+
+    pub fn selected_dir(&self) -> PathBuf {
+        match self.selected_entry() {
+            Some(entry) if entry.is_dir => entry.path.clone(),
+            Some(entry) => entry
+                .path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| self.root.clone()),
+            None => self.root.clone(),
+        }
+    }
+
+    pub fn refresh(&mut self) -> bool {
+        let scanned = self.scan().read();
+        self.apply(scanned)
+    }
+
+    fn scan(&self) -> TreeScan {
+        TreeScan {
+            root: self.root.clone(),
+            expanded: self.expanded.clone(),
+            show_hidden: self.show_hidden,
+        }
+    }
+
+    pub fn begin_scan(&mut self) -> Option<TreeScan> {
+        if self.scanning {
+            return None;
+        }
+        self.scanning = true;
+        Some(self.scan())
+    }
+
+    /// Applies a background refresh, unless the tree was changed meanwhile.
+    pub fn finish_scan(&mut self, scanned: ScannedTree) -> bool {
+        self.scanning = false;
+        scanned.scan == self.scan() && self.apply(scanned)
+    }
+
+    fn apply(&mut self, scanned: ScannedTree) -> bool {
+        let selected_path = self.selected_entry().map(|e| e.path.clone());
+        self.expanded = scanned.expanded;
+
+        let changed = scanned.entries != self.entries;
+        self.entries = scanned.entries;
+        if let Some(path) = selected_path
+            && self.select_path(&path)
+        {
+            return changed;
+        }
+        self.selected = self.selected.min(self.entries.len().saturating_sub(1));
+        changed
+    }
+}
+
+impl TreeScan {
+    pub fn read(self) -> ScannedTree {
+        let mut entries = Vec::new();
+        self.collect(&self.root, 0, &mut entries);
+        let expanded = self.expanded.iter().filter(|p| p.is_dir()).cloned().collect();
+        ScannedTree {
+            scan: self,
+            entries,
+            expanded,
+        }
+    }
+
+    fn collect(&self, dir: &Path, depth: usize, out: &mut Vec<TreeEntry>) {
+        let Ok(read_dir) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut children: Vec<TreeEntry> = read_dir
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().to_str()?.to_string();
+                if !self.show_hidden && name.starts_with('.') {
+                    return None;
+                }
+                let path = e.path();
+                let is_dir = path.is_dir();
+                Some(TreeEntry {
+                    expanded: is_dir && self.expanded.contains(&path),
+                    path,
+                    name,
+                    depth,
+                    is_dir,
+                })
+            })
+            .collect();
+        children.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+
+        for child in children {
+            let recurse = child.expanded;
+            let path = child.path.clone();
+            out.push(child);
+            if recurse {
+                self.collect(&path, depth + 1, out);
+            }
+        }
+    }
+}
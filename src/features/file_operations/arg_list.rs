@@ -0,0 +1,76 @@
+use crate::features::navigation::GotoFeature;
+use crate::state::EditorState;
+use crate::utils::paths;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgFile {
+    pub path: PathBuf,
+    /// Zero-based line and column given with the file, used the first time
+    /// it is opened.
+    pub position: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ArgList {
+    files: Vec<ArgFile>,
+    read_only: bool,
+}
+
+impl ArgList {
+    pub fn new(files: Vec<ArgFile>, read_only: bool) -> Self {
+        Self { files, read_only }
+    }
+
+    pub fn files(&self) -> &[ArgFile] {
+        &self.files
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        self.position(Some(path)).is_some()
+    }
+
+    pub fn next_after(&self, current: Option<&Path>) -> Option<&PathBuf> {
+        let next = match self.position(current) {
+            Some(index) => self.files.get(index + 1),
+            None => self.files.first(),
+        };
+        next.map(|f| &f.path)
+    }
+
+    pub fn previous_before(&self, current: Option<&Path>) -> Option<&PathBuf> {
+        let previous = match self.position(current) {
+            Some(index) => index.checked_sub(1).and_then(|i| self.files.get(i)),
+            None => self.files.last(),
+        };
+        previous.map(|f| &f.path)
+    }
+
+    pub fn apply_position(state: &mut EditorState) -> bool {
+        let current = state.document().file_path().cloned();
+        let arg_list = state.arg_list_mut();
+        let Some(index) = arg_list.position(current.as_deref()) else {
+            return false;
+        };
+        let Some((line, column)) = arg_list.files[index].position.take() else {
+            return false;
+        };
+        if let Some(progress) = state.loading_mut() {
+            progress.position = Some((line, column));
+            return true;
+        }
+        let (buffer, cursor, viewport) = state.buffer_cursor_viewport();
+        GotoFeature::goto(cursor, buffer, line, column);
+        viewport.adjust_for_cursor(cursor.line);
+        true
+    }
+
+    fn position(&self, current: Option<&Path>) -> Option<usize> {
+        let current = paths::absolute(current?);
+        self.files.iter().position(|f| paths::absolute(&f.path) == current)
+    }
+}
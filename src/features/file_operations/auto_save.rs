@@ -0,0 +1,70 @@
+use crate::core::cursor::CursorMovement;
+use crate::features::file_operations::SaveFile;
+use crate::state::EditorState;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoSaveSettings {
+    pub idle_delay: Option<Duration>,
+    pub on_focus_lost: bool,
+}
+
+impl AutoSaveSettings {
+    pub const DEFAULT_IDLE_DELAY: Duration = Duration::from_secs(5);
+
+    pub fn off() -> Self {
+        Self {
+            idle_delay: None,
+            on_focus_lost: false,
+        }
+    }
+
+    pub fn enabled() -> Self {
+        Self {
+            idle_delay: Some(Self::DEFAULT_IDLE_DELAY),
+            on_focus_lost: true,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.idle_delay.is_some() || self.on_focus_lost
+    }
+}
+
+impl Default for AutoSaveSettings {
+    fn default() -> Self {
+        Self::off()
+    }
+}
+
+pub struct AutoSave;
+
+impl AutoSave {
+    pub fn run(state: &mut EditorState) {
+        let document = state.document();
+        if !document.is_modified() || document.is_read_only() || document.file_path().is_none() {
+            return;
+        }
+
+        let file_name = document.file_name();
+        let backup = state.backup_policy();
+        match SaveFile::execute(state.document_mut(), backup) {
+            Ok(_) => {
+                let (buffer, cursor) = state.buffer_cursor();
+                CursorMovement::clamp(cursor, buffer);
+                state
+                    .message_mut()
+                    .set(format!("Auto-saved: {}", file_name))
+            }
+            Err(e) => state
+                .message_mut()
+                .set(format!("Auto-save failed: {}", e)),
+        }
+    }
+
+    pub fn on_focus_lost(state: &mut EditorState) {
+        if state.auto_save().on_focus_lost {
+            Self::run(state);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::core::buffer::RopeBuffer;
+use crate::core::document::{Backup, BackupEntry, Document};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub struct BackupFiles;
+
+impl BackupFiles {
+    pub fn list(file_path: &Path) -> Vec<BackupEntry> {
+        Backup::list_for(file_path)
+    }
+
+    pub fn open(entry: &BackupEntry) -> io::Result<Document> {
+        let content = fs::read_to_string(&entry.path)?;
+        let mut document = Document::from_buffer(RopeBuffer::from_string(&content), None);
+        document.set_read_only(true);
+        Ok(document)
+    }
+
+    pub fn restore(document: &mut Document, entry: &BackupEntry) -> io::Result<()> {
+        let content = fs::read_to_string(&entry.path)?;
+        let buffer = document.buffer_mut()?;
+        buffer.replace(0..buffer.len_chars(), &content);
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use crate::app::TaskSpawner;
+use crate::core::buffer::RopeBuffer;
+use crate::core::document::{DiskStamp, Document, FileIO};
+use crate::features::navigation::GotoFeature;
+use crate::state::EditorState;
+use crate::utils::text;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+const MIB: u64 = 1024 * 1024;
+const READ_CHUNK: usize = MIB as usize;
+
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LargeFileSettings {
+    pub stream_threshold: u64,
+    /// Files at least this big are memory-mapped read-only; `None` disables it.
+    pub mmap_threshold: Option<u64>,
+}
+
+impl Default for LargeFileSettings {
+    fn default() -> Self {
+        Self {
+            stream_threshold: 16 * MIB,
+            mmap_threshold: Some(256 * MIB),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadProgress {
+    pub generation: u64,
+    pub bytes_read: u64,
+    pub total: u64,
+    pub read_only: bool,
+    pub position: Option<(usize, usize)>,
+}
+
+impl LoadProgress {
+    pub fn percent(&self) -> u64 {
+        (self.bytes_read * 100).checked_div(self.total).unwrap_or(100).min(100)
+    }
+}
+
+pub enum LargeFileOpen {
+    Mapped(Document),
+    Streaming(Document, LoadProgress),
+}
+
+pub struct LargeFile;
+
+impl LargeFile {
+    /// Opens `path` specially if it is over one of the size thresholds;
+    /// returns `None` for files that should be loaded normally.
+    pub fn open(
+        path: &Path,
+        settings: &LargeFileSettings,
+        tasks: &TaskSpawner,
+    ) -> io::Result<Option<LargeFileOpen>> {
+        let metadata = fs::metadata(path)?;
+        let size = metadata.len();
+        if !metadata.is_file() || size < settings.stream_threshold {
+            return Ok(None);
+        }
+
+        if settings.mmap_threshold.is_some_and(|t| size >= t) {
+            match FileIO::map_file(path) {
+                Ok(document) => return Ok(Some(LargeFileOpen::Mapped(document))),
+                // Not UTF-8 (or not mappable): fall back to a lossy stream.
+                Err(e) if e.kind() == ErrorKind::InvalidData || e.kind() == ErrorKind::Unsupported => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let file = File::open(path)?;
+        let mut document = Document::from_buffer(RopeBuffer::new(), Some(path.to_path_buf()));
+        let progress = LoadProgress {
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+            bytes_read: 0,
+            total: size,
+            read_only: !FileIO::is_writable(path),
+            position: None,
+        };
+        document.set_read_only(true);
+        document.set_disk_stamp(Some(DiskStamp {
+            len: size,
+            modified: metadata.modified().ok(),
+        }));
+        Self::spawn_reader(file, progress.generation, tasks);
+        Ok(Some(LargeFileOpen::Streaming(document, progress)))
+    }
+
+    fn spawn_reader(file: File, generation: u64, tasks: &TaskSpawner) {
+        let tasks = tasks.clone();
+        thread::spawn(move || {
+            let streamed = text::stream_utf8(file, READ_CHUNK, |text, n| {
+                tasks.update(move |state| {
+                    Self::append(state, generation, &text, n as u64);
+                })
+            });
+            if let Some((rest, result)) = streamed {
+                let error = result.err().map(|e| e.to_string());
+                tasks.update(move |state| Self::finish(state, generation, &rest, error));
+            }
+        });
+    }
+
+    fn append(state: &mut EditorState, generation: u64, text: &str, bytes: u64) -> bool {
+        let Some(progress) = state.loading_mut().filter(|p| p.generation == generation) else {
+            return false;
+        };
+        progress.bytes_read += bytes;
+
+        let document = state.document_mut();
+        document.load_buffer().append(text);
+        true
+    }
+
+    fn finish(state: &mut EditorState, generation: u64, rest: &str, error: Option<String>) {
+        if !Self::append(state, generation, rest, 0) {
+            return;
+        }
+        let Some(progress) = state.take_loading() else {
+            return;
+        };
+        state.document_mut().set_read_only(progress.read_only);
+        if let Some((line, column)) = progress.position {
+            let (buffer, cursor, viewport) = state.buffer_cursor_viewport();
+            GotoFeature::goto(cursor, buffer, line, column);
+            viewport.adjust_for_cursor(cursor.line);
+        }
+        let file_name = state.document().file_name();
+        let message = match error {
+            Some(e) => format!("Loading {} stopped early: {}", file_name, e),
+            None => format!(
+                "Loaded {} ({} lines)",
+                file_name,
+                state.document().buffer().len_lines()
+            ),
+        };
+        state.message_mut().set(message);
+        state.check_recovery();
+    }
+}
@@ -1,7 +1,21 @@
+mod arg_list;
+mod auto_save;
+mod backups;
+mod large_file;
 mod open;
+mod path_completion;
+mod recover;
+mod reload;
 mod save;
 mod save_as;
 
-pub use open::OpenFile;
+pub use arg_list::{ArgFile, ArgList};
+pub use auto_save::{AutoSave, AutoSaveSettings};
+pub use backups::BackupFiles;
+pub use large_file::{LargeFile, LargeFileOpen, LargeFileSettings, LoadProgress};
+pub use open::{OpenFile, OpenedFile};
+pub use path_completion::{PathCandidate, PathCompletion};
+pub use recover::{DiffLine, RecoverFile, SwapRecovery};
+pub use reload::ReloadFile;
 pub use save::SaveFile;
 pub use save_as::SaveFileAs;
\ No newline at end of file
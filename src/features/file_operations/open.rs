@@ -1,10 +1,80 @@
+use crate::app::TaskSpawner;
+use crate::core::buffer::RopeBuffer;
 use crate::core::document::{Document, FileIO};
+use crate::features::file_operations::{LargeFile, LargeFileOpen, LargeFileSettings, LoadProgress};
+use crate::features::editorconfig::EditorConfig;
+use crate::features::session::RecentFiles;
+use crate::utils::paths;
 use std::io;
+use std::path::{Path, PathBuf};
+
+pub struct OpenedFile {
+    pub document: Document,
+    pub created: bool,
+    pub mapped: bool,
+    pub loading: Option<LoadProgress>,
+}
 
 pub struct OpenFile;
 
 impl OpenFile {
     pub fn execute(path: &str) -> io::Result<Document> {
-        FileIO::load_from_file(path)
+        FileIO::load_from_file(path, EditorConfig::resolve(Path::new(path)))
+    }
+
+    pub fn open_or_create(path: &str) -> io::Result<(Document, bool)> {
+        let expanded = paths::expand_tilde(path);
+        let path_str = expanded.to_string_lossy();
+        match Self::execute(&path_str) {
+            Ok(document) => Ok((document, false)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let mut document = Document::from_buffer(RopeBuffer::new(), Some(PathBuf::from(&*path_str)));
+                document.set_settings(EditorConfig::resolve(&expanded));
+                Ok((document, true))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn open_with(
+        path: &str,
+        settings: &LargeFileSettings,
+        tasks: Option<&TaskSpawner>,
+    ) -> io::Result<OpenedFile> {
+        let expanded = paths::expand_tilde(path);
+        let large = match tasks {
+            Some(tasks) if expanded.is_file() => LargeFile::open(&expanded, settings, tasks)?,
+            _ => None,
+        };
+        let mut opened = match large {
+            Some(LargeFileOpen::Mapped(document)) => OpenedFile {
+                document,
+                created: false,
+                mapped: true,
+                loading: None,
+            },
+            Some(LargeFileOpen::Streaming(document, progress)) => OpenedFile {
+                document,
+                created: false,
+                mapped: false,
+                loading: Some(progress),
+            },
+            None => {
+                let (document, created) = Self::open_or_create(path)?;
+                OpenedFile {
+                    document,
+                    created,
+                    mapped: false,
+                    loading: None,
+                }
+            }
+        };
+        if opened.mapped || opened.loading.is_some() {
+            opened.document.set_settings(EditorConfig::resolve(&expanded));
+        }
+        if !opened.created {
+            let _ = RecentFiles::record(&expanded);
+        }
+        Ok(opened)
     }
-}
\ No newline at end of file
+}
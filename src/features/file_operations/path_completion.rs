@@ -0,0 +1,78 @@
+use crate::utils::paths;
+use std::fs;
+use std::path::MAIN_SEPARATOR;
+
+const MAX_CANDIDATES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathCandidate {
+    pub name: String,
+    pub value: String,
+    pub is_dir: bool,
+}
+
+pub struct PathCompletion;
+
+impl PathCompletion {
+    pub fn candidates(input: &str) -> Vec<PathCandidate> {
+        let (dir_part, prefix) = match input.rfind(MAIN_SEPARATOR) {
+            Some(idx) => input.split_at(idx + 1),
+            None if input == "~" => return Self::candidates(&format!("~{}", MAIN_SEPARATOR)),
+            None => ("", input),
+        };
+
+        let dir = if dir_part.is_empty() {
+            paths::absolute(".".as_ref())
+        } else {
+            paths::expand_tilde(dir_part)
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Vec::new();
+        };
+
+        let show_hidden = prefix.starts_with('.');
+        let mut candidates: Vec<PathCandidate> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.to_string();
+                if !name.starts_with(prefix) || (!show_hidden && name.starts_with('.')) {
+                    return None;
+                }
+                let is_dir = entry.path().is_dir();
+                let mut value = format!("{}{}", dir_part, name);
+                if is_dir {
+                    value.push(MAIN_SEPARATOR);
+                }
+                Some(PathCandidate { name, value, is_dir })
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+        candidates.truncate(MAX_CANDIDATES);
+        candidates
+    }
+
+    pub fn complete(input: &str, candidates: &[PathCandidate]) -> Option<String> {
+        let first = candidates.first()?;
+        if candidates.len() == 1 {
+            return Some(first.value.clone());
+        }
+
+        let mut common: &str = &first.value;
+        for candidate in &candidates[1..] {
+            let shared = common
+                .char_indices()
+                .zip(candidate.value.chars())
+                .find(|((_, a), b)| a != b)
+                .map(|((i, _), _)| i)
+                .unwrap_or(common.len().min(candidate.value.len()));
+            common = &common[..shared];
+        }
+
+        if common.len() > input.len() {
+            Some(common.to_string())
+        } else {
+            None
+        }
+    }
+}
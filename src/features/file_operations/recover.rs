@@ -0,0 +1,132 @@
+use crate::core::document::{Document, SwapFile};
+use std::io;
+use std::path::PathBuf;
+
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+pub struct SwapRecovery {
+    pub file_path: PathBuf,
+    pub pid: u32,
+    pub owner_running: bool,
+    pub content: String,
+    pub show_diff: bool,
+    pub diff: Vec<DiffLine>,
+    pub scroll: usize,
+}
+
+pub struct RecoverFile;
+
+impl RecoverFile {
+    pub fn check(document: &Document) -> Option<SwapRecovery> {
+        let path = document.file_path()?;
+        if !SwapFile::exists_for(path) {
+            return None;
+        }
+        let swap = SwapFile::read(path).ok()?;
+        let current = document.buffer().to_string();
+        let owner_running = swap.owner_running();
+        // An identical swap left by a dead process holds nothing to recover.
+        if swap.content == current && !owner_running {
+            let _ = SwapFile::remove_for(path);
+            return None;
+        }
+        Some(SwapRecovery {
+            file_path: path.clone(),
+            pid: swap.pid,
+            owner_running,
+            diff: Self::diff(&current, &swap.content),
+            content: swap.content,
+            show_diff: false,
+            scroll: 0,
+        })
+    }
+
+    pub fn recover(document: &mut Document, recovery: &SwapRecovery) {
+        document.load_text(&recovery.content);
+        document.mark_modified();
+        document.mark_swapped();
+    }
+
+    pub fn discard(recovery: &SwapRecovery) -> io::Result<()> {
+        SwapFile::remove_for(&recovery.file_path)
+    }
+
+    pub fn diff(old: &str, new: &str) -> Vec<DiffLine> {
+        let old_lines: Vec<&str> = old.lines().collect();
+        let new_lines: Vec<&str> = new.lines().collect();
+
+        let prefix = old_lines
+            .iter()
+            .zip(&new_lines)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let suffix = old_lines[prefix..]
+            .iter()
+            .rev()
+            .zip(new_lines[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let old_mid = &old_lines[prefix..old_lines.len() - suffix];
+        let new_mid = &new_lines[prefix..new_lines.len() - suffix];
+
+        let mut result: Vec<DiffLine> = old_lines[..prefix]
+            .iter()
+            .map(|l| DiffLine::Same(l.to_string()))
+            .collect();
+        result.extend(Self::diff_middle(old_mid, new_mid));
+        result.extend(
+            old_lines[old_lines.len() - suffix..]
+                .iter()
+                .map(|l| DiffLine::Same(l.to_string())),
+        );
+        result
+    }
+
+    fn diff_middle(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+        if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+            let mut result: Vec<DiffLine> =
+                old.iter().map(|l| DiffLine::Removed(l.to_string())).collect();
+            result.extend(new.iter().map(|l| DiffLine::Added(l.to_string())));
+            return result;
+        }
+
+        let (n, m) = (old.len(), new.len());
+        let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old[i] == new[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut result = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if old[i] == new[j] {
+                result.push(DiffLine::Same(old[i].to_string()));
+                i += 1;
+                j += 1;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                result.push(DiffLine::Removed(old[i].to_string()));
+                i += 1;
+            } else {
+                result.push(DiffLine::Added(new[j].to_string()));
+                j += 1;
+            }
+        }
+        result.extend(old[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+        result.extend(new[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+        result
+    }
+}
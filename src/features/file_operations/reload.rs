@@ -0,0 +1,57 @@
+use crate::core::buffer::RopeBuffer;
+use crate::core::document::{DiskStamp, Document, FileIO};
+use std::io;
+use std::path::Path;
+
+pub struct ReloadFile;
+
+impl ReloadFile {
+    /// Large documents (mapped or streamed in) are compared by size and
+    /// modification time, so the check never reads them on the UI thread.
+    pub fn differs_from_disk(document: &Document) -> io::Result<bool> {
+        let Some(path) = document.file_path() else {
+            return Ok(false);
+        };
+        if let Some(stamp) = document.disk_stamp() {
+            return Ok(DiskStamp::read(path)? != stamp);
+        }
+        let content = FileIO::read_text(path, document.settings().charset)?;
+        Ok(content != document.buffer().to_string())
+    }
+
+    pub fn execute(document: &mut Document) -> io::Result<()> {
+        let Some(path) = document.file_path().cloned() else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "No file path set"));
+        };
+        if document.buffer().is_mapped() {
+            return Self::remap(document, &path);
+        }
+        let stamp = DiskStamp::read(&path)?;
+        let content = FileIO::read_text(&path, document.settings().charset)?;
+        document.load_text(&content);
+        document.mark_saved();
+        if document.disk_stamp().is_some() {
+            document.set_disk_stamp(Some(stamp));
+        }
+        Ok(())
+    }
+
+    /// Maps the file again. The old mapping is dropped even on failure, as
+    /// reading it after the file shrank would crash.
+    fn remap(document: &mut Document, path: &Path) -> io::Result<()> {
+        let remapped = DiskStamp::read(path).and_then(|stamp| Ok((stamp, RopeBuffer::map_file(path)?)));
+        let result = match remapped {
+            Ok((stamp, buffer)) => {
+                *document.load_buffer() = buffer;
+                document.set_disk_stamp(Some(stamp));
+                Ok(())
+            }
+            Err(e) => {
+                *document.load_buffer() = RopeBuffer::new();
+                Err(e)
+            }
+        };
+        document.mark_saved();
+        result
+    }
+}
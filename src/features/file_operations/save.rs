@@ -1,10 +1,10 @@
-use crate::core::document::{Document, FileIO};
+use crate::core::document::{BackupPolicy, Document, FileIO};
 use std::io;
 
 pub struct SaveFile;
 
 impl SaveFile {
-    pub fn execute(document: &mut Document) -> io::Result<()> {
-        FileIO::save_document(document)
+    pub fn execute(document: &mut Document, backup: BackupPolicy) -> io::Result<()> {
+        FileIO::save_document(document, backup)
     }
 }
\ No newline at end of file
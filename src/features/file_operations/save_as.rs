@@ -1,10 +1,16 @@
-use crate::core::document::{Document, FileIO};
+use crate::core::document::{BackupPolicy, Document, FileIO};
+use crate::features::editorconfig::EditorConfig;
+use crate::features::session::RecentFiles;
 use std::io;
+use std::path::Path;
 
 pub struct SaveFileAs;
 
 impl SaveFileAs {
-    pub fn execute(document: &mut Document, path: &str) -> io::Result<()> {
-        FileIO::save_document_as(document, path)
+    pub fn execute(document: &mut Document, path: &str, backup: BackupPolicy) -> io::Result<()> {
+        document.set_settings(EditorConfig::resolve(Path::new(path)));
+        FileIO::save_document_as(document, path, backup)?;
+        let _ = RecentFiles::record(Path::new(path));
+        Ok(())
     }
 }
\ No newline at end of file
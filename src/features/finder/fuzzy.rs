@@ -0,0 +1,154 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    pub positions: Vec<usize>,
+}
+
+pub struct FuzzyMatcher;
+
+impl FuzzyMatcher {
+    const MATCH: i64 = 16;
+    const CONSECUTIVE: i64 = 24;
+    const BOUNDARY: i64 = 32;
+    const BASENAME: i64 = 8;
+    const GAP: i64 = 1;
+
+    pub fn score(query: &str, candidate: &str) -> Option<FuzzyMatch> {
+        let mut positions = Vec::with_capacity(query.len());
+        let score = Self::run(query, candidate, Some(&mut positions))?;
+        Some(FuzzyMatch { score, positions })
+    }
+
+    pub fn score_only(query: &str, candidate: &str) -> Option<i64> {
+        Self::run(query, candidate, None)
+    }
+
+    /// Whether every query char occurs in order, with the same case rule as
+    /// scoring. Cheaper than `score_only` for filtering.
+    pub fn is_match(query: &str, candidate: &str) -> bool {
+        let smart_case = Self::is_smart_case(query);
+        let mut chars = candidate.chars();
+        query
+            .chars()
+            .all(|q| chars.any(|c| Self::chars_match(smart_case, q, c)))
+    }
+
+    pub fn eq_ignore_case(q: char, c: char) -> bool {
+        if q.is_ascii() && c.is_ascii() {
+            q.eq_ignore_ascii_case(&c)
+        } else {
+            q.to_lowercase().eq(c.to_lowercase())
+        }
+    }
+
+    fn run(query: &str, candidate: &str, mut positions: Option<&mut Vec<usize>>) -> Option<i64> {
+        let length_penalty = (candidate.len() as i64) / 8;
+        if query.is_empty() {
+            return Some(-length_penalty);
+        }
+
+        let basename_start = candidate.rfind(['/', '\\']).map_or(0, |i| i + 1);
+        let smart_case = Self::is_smart_case(query);
+
+        let mut chars = candidate.char_indices().enumerate();
+        let mut prev: Option<char> = None;
+        let mut last_match: Option<usize> = None;
+        let mut score = 0;
+
+        for q in query.chars() {
+            let (index, byte) = loop {
+                let (index, (byte, ch)) = chars.next()?;
+                let before = prev.replace(ch);
+                if Self::chars_match(smart_case, q, ch) {
+                    let boundary = before.is_none_or(|p| Self::is_boundary(p, ch));
+                    if boundary {
+                        score += Self::BOUNDARY;
+                    }
+                    break (index, byte);
+                }
+            };
+            score += Self::MATCH;
+            match last_match {
+                Some(last) if last + 1 == index => score += Self::CONSECUTIVE,
+                Some(last) => score -= Self::GAP * (index - last - 1).min(16) as i64,
+                None => {}
+            }
+            if byte >= basename_start {
+                score += Self::BASENAME;
+            }
+            if let Some(positions) = positions.as_deref_mut() {
+                positions.push(index);
+            }
+            last_match = Some(index);
+        }
+
+        Some(score - length_penalty)
+    }
+
+    fn is_smart_case(query: &str) -> bool {
+        query.chars().any(|c| c.is_uppercase())
+    }
+
+    fn chars_match(smart_case: bool, q: char, c: char) -> bool {
+        if smart_case { q == c } else { Self::eq_ignore_case(q, c) }
+    }
+
+    fn is_boundary(prev: char, current: char) -> bool {
+        matches!(prev, '/' | '\\' | '_' | '-' | '.' | ' ')
+            || (prev.is_lowercase() && current.is_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lowercase_query_ignores_case() {
+        assert!(FuzzyMatcher::is_match("readme", "README.md"));
+        assert!(FuzzyMatcher::score("readme", "README.md").is_some());
+    }
+
+    #[test]
+    fn uppercase_query_is_case_sensitive() {
+        assert!(!FuzzyMatcher::is_match("Main", "src/main.rs"));
+        assert!(FuzzyMatcher::score("Main", "src/main.rs").is_none());
+        assert!(FuzzyMatcher::is_match("Main", "src/Main.java"));
+    }
+
+    #[test]
+    fn filter_agrees_with_scoring() {
+        let candidates = ["src/main.rs", "src/Main.java", "docs/MAIN.md", "lib.rs"];
+        for query in ["main", "Main", "MAIN", "mr", "M"] {
+            for candidate in candidates {
+                assert_eq!(
+                    FuzzyMatcher::is_match(query, candidate),
+                    FuzzyMatcher::score_only(query, candidate).is_some(),
+                    "{query} / {candidate}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn positions_are_char_indices() {
+        let found = FuzzyMatcher::score("fb", "föo/bar").unwrap();
+        assert_eq!(found.positions, vec![0, 4]);
+    }
+
+    #[test]
+    fn prefers_consecutive_and_basename_matches() {
+        let basename = FuzzyMatcher::score_only("main", "src/main.rs").unwrap();
+        let scattered = FuzzyMatcher::score_only("main", "m/a/i/n/other.rs").unwrap();
+        let in_dir = FuzzyMatcher::score_only("main", "main/other.rs").unwrap();
+        assert!(basename > scattered);
+        assert!(basename > in_dir);
+    }
+
+    #[test]
+    fn empty_query_prefers_shorter_paths() {
+        let short = FuzzyMatcher::score_only("", "a.rs").unwrap();
+        let long = FuzzyMatcher::score_only("", "some/very/long/path/name.rs").unwrap();
+        assert!(short > long);
+    }
+}
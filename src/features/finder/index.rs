@@ -0,0 +1,69 @@
+use crate::app::TaskSpawner;
+use ignore::WalkBuilder;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+const BATCH_SIZE: usize = 2048;
+const SNIFF_BYTES: usize = 512;
+const BINARY_EXTENSIONS: &[&str] = &[
+    "a", "bin", "bmp", "class", "dll", "dylib", "exe", "gif", "gz", "ico", "jar", "jpeg", "jpg",
+    "mp3", "mp4", "o", "obj", "pdf", "png", "pyc", "rlib", "so", "tar", "ttf", "wasm",
+    "webp", "woff", "woff2", "xz", "zip", "zst",
+];
+
+pub struct FileIndexer;
+
+impl FileIndexer {
+    pub fn spawn(root: PathBuf, generation: u64, tasks: &TaskSpawner) {
+        let tasks = tasks.clone();
+        std::thread::spawn(move || {
+            let mut batch = Vec::with_capacity(BATCH_SIZE);
+            let walker = WalkBuilder::new(&root)
+                .hidden(true)
+                .git_ignore(true)
+                .git_global(true)
+                .git_exclude(true)
+                .ignore(true)
+                .require_git(false)
+                .build();
+
+            for entry in walker.filter_map(|e| e.ok()) {
+                if !entry.file_type().is_some_and(|t| t.is_file()) || Self::is_binary(entry.path()) {
+                    continue;
+                }
+                let relative = entry.path().strip_prefix(&root).unwrap_or(entry.path());
+                batch.push(relative.to_string_lossy().into_owned());
+
+                if batch.len() >= BATCH_SIZE {
+                    let paths = std::mem::replace(&mut batch, Vec::with_capacity(BATCH_SIZE));
+                    if !tasks.update(move |state| state.file_picker_mut().extend(generation, paths)) {
+                        return;
+                    }
+                }
+            }
+
+            tasks.update(move |state| {
+                let picker = state.file_picker_mut();
+                picker.extend(generation, batch);
+                picker.finish(generation);
+            });
+        });
+    }
+
+    pub fn is_binary(path: &Path) -> bool {
+        let by_extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| BINARY_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+        if by_extension {
+            return true;
+        }
+
+        let mut buffer = [0u8; SNIFF_BYTES];
+        match File::open(path).and_then(|mut f| f.read(&mut buffer)) {
+            Ok(n) => buffer[..n].contains(&0),
+            Err(_) => true,
+        }
+    }
+}
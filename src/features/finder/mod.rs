@@ -0,0 +1,7 @@
+mod fuzzy;
+mod index;
+mod picker;
+
+pub use fuzzy::{FuzzyMatch, FuzzyMatcher};
+pub use index::FileIndexer;
+pub use picker::{FilePicker, FinderResult};
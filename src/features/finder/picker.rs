@@ -0,0 +1,189 @@
+use super::FuzzyMatcher;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+const MAX_RESULTS: usize = 1000;
+const PREVIEW_LINES: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinderResult {
+    pub index: usize,
+    pub score: i64,
+    pub positions: Vec<usize>,
+}
+
+#[derive(Default)]
+pub struct FilePicker {
+    root: PathBuf,
+    paths: Vec<String>,
+    matched: Vec<usize>,
+    results: Vec<FinderResult>,
+    query: String,
+    selected: usize,
+    generation: u64,
+    indexing: bool,
+    preview: Vec<String>,
+    preview_index: Option<usize>,
+}
+
+impl FilePicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start(&mut self, root: PathBuf) -> u64 {
+        self.generation += 1;
+        self.root = root;
+        self.paths.clear();
+        self.matched.clear();
+        self.results.clear();
+        self.query.clear();
+        self.selected = 0;
+        self.indexing = true;
+        self.preview.clear();
+        self.preview_index = None;
+        self.generation
+    }
+
+    pub fn extend(&mut self, generation: u64, paths: Vec<String>) {
+        if generation != self.generation || paths.is_empty() {
+            return;
+        }
+        let start = self.paths.len();
+        self.paths.extend(paths);
+        let new_matches: Vec<usize> = (start..self.paths.len())
+            .filter(|&i| FuzzyMatcher::is_match(&self.query, &self.paths[i]))
+            .collect();
+        self.matched.extend(&new_matches);
+        self.merge_results(&new_matches);
+    }
+
+    pub fn finish(&mut self, generation: u64) {
+        if generation == self.generation {
+            self.indexing = false;
+        }
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        let narrowing = !self.query.is_empty() && query.starts_with(self.query.as_str());
+        self.query = query.to_string();
+
+        let pool: Vec<usize> = if narrowing {
+            std::mem::take(&mut self.matched)
+        } else {
+            (0..self.paths.len()).collect()
+        };
+        self.matched = pool
+            .into_iter()
+            .filter(|&i| FuzzyMatcher::is_match(query, &self.paths[i]))
+            .collect();
+        self.results.clear();
+        self.selected = 0;
+        let matched = self.matched.clone();
+        self.merge_results(&matched);
+    }
+
+    pub fn results(&self) -> &[FinderResult] {
+        &self.results
+    }
+
+    pub fn path(&self, result: &FinderResult) -> &str {
+        &self.paths[result.index]
+    }
+
+    pub fn total(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn matched_count(&self) -> usize {
+        self.matched.len()
+    }
+
+    pub fn is_indexing(&self) -> bool {
+        self.indexing
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+            self.refresh_preview();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+            self.refresh_preview();
+        }
+    }
+
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        self.results
+            .get(self.selected)
+            .map(|r| self.root.join(&self.paths[r.index]))
+    }
+
+    pub fn preview(&self) -> &[String] {
+        &self.preview
+    }
+
+    fn merge_results(&mut self, indices: &[usize]) {
+        let previous = self.results.get(self.selected).map(|r| r.index);
+        for &index in indices {
+            if let Some(score) = FuzzyMatcher::score_only(&self.query, &self.paths[index]) {
+                self.results.push(FinderResult {
+                    index,
+                    score,
+                    positions: Vec::new(),
+                });
+            }
+        }
+        let order = |a: &FinderResult, b: &FinderResult| {
+            b.score.cmp(&a.score).then_with(|| a.index.cmp(&b.index))
+        };
+        if self.results.len() > MAX_RESULTS {
+            self.results.select_nth_unstable_by(MAX_RESULTS, order);
+            self.results.truncate(MAX_RESULTS);
+        }
+        self.results.sort_by(order);
+        for result in &mut self.results {
+            if result.positions.is_empty() {
+                result.positions = FuzzyMatcher::score(&self.query, &self.paths[result.index])
+                    .map(|m| m.positions)
+                    .unwrap_or_default();
+            }
+        }
+        self.selected = previous
+            .and_then(|p| self.results.iter().position(|r| r.index == p))
+            .unwrap_or(0);
+        self.refresh_preview();
+    }
+
+    fn refresh_preview(&mut self) {
+        let index = self.results.get(self.selected).map(|r| r.index);
+        if index == self.preview_index {
+            return;
+        }
+        self.preview_index = index;
+        self.preview = self
+            .selected_path()
+            .map(|p| Self::read_preview(&p))
+            .unwrap_or_default();
+    }
+
+    fn read_preview(path: &Path) -> Vec<String> {
+        match File::open(path) {
+            Ok(file) => BufReader::new(file)
+                .lines()
+                .take(PREVIEW_LINES)
+                .map_while(Result::ok)
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
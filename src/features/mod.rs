@@ -1,4 +1,10 @@
 pub mod editing;
+pub mod editorconfig;
+pub mod explorer;
 pub mod command_palette;
 pub mod file_operations;
-pub mod navigation;
\ No newline at end of file
+pub mod finder;
+pub mod navigation;
+pub mod pager;
+pub mod session;
+pub mod syntax;
\ No newline at end of file
@@ -0,0 +1,265 @@
+use crate::core::buffer::RopeBuffer;
+use std::ops::Range;
+
+/// Brackets further apart than this are not matched, so an unbalanced one
+/// cannot make every redraw walk the whole file.
+const MAX_DISTANCE: usize = 100_000;
+
+/// Bracket pairs, written as consecutive opener/closer chars: `()[]{}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketPairs(Vec<(char, char)>);
+
+impl BracketPairs {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let chars: Vec<char> = spec.chars().collect();
+        if !chars.len().is_multiple_of(2) {
+            return Err("must list brackets as opener/closer pairs".to_string());
+        }
+        let pairs: Vec<(char, char)> = chars.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+        for (i, ch) in chars.iter().enumerate() {
+            if chars[..i].contains(ch) {
+                return Err(format!("lists '{}' more than once", ch));
+            }
+        }
+        Ok(Self(pairs))
+    }
+
+    pub fn closer(&self, open: char) -> Option<char> {
+        self.0.iter().find(|pair| pair.0 == open).map(|pair| pair.1)
+    }
+
+    fn find(&self, ch: char) -> Option<((char, char), bool)> {
+        self.0.iter().find_map(|&(open, close)| match ch {
+            _ if ch == open => Some(((open, close), true)),
+            _ if ch == close => Some(((open, close), false)),
+            _ => None,
+        })
+    }
+}
+
+impl Default for BracketPairs {
+    fn default() -> Self {
+        Self(vec![('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')])
+    }
+}
+
+pub struct BracketFeature;
+
+impl BracketFeature {
+    /// The bracket at `idx`, or else the one just before it, and the index
+    /// of its partner. `is_code` tells code from string and comment text;
+    /// only brackets on the same side as the first one are counted.
+    pub fn find_match(
+        buffer: &RopeBuffer,
+        idx: usize,
+        pairs: &BracketPairs,
+        is_code: &mut impl FnMut(usize) -> bool,
+    ) -> Option<(usize, usize)> {
+        let at = |i: usize| buffer.chars_at(i).next().and_then(|ch| pairs.find(ch)).map(|p| (i, p));
+        let (start, (pair, opens)) = at(idx).or_else(|| idx.checked_sub(1).and_then(at))?;
+        let code = is_code(start);
+        let mut same_side = |i: usize| is_code(i) == code;
+        let partner = if opens {
+            Self::scan_forward(buffer, start + 1, pair, &mut same_side)
+        } else {
+            Self::scan_backward(buffer, start, pair, &mut same_side)
+        }?;
+        Some((start, partner))
+    }
+
+    /// The innermost pair of code brackets around `range`, as the indices of
+    /// its opener and closer. An opener at an empty range encloses it.
+    pub fn enclosing(
+        buffer: &RopeBuffer,
+        range: Range<usize>,
+        pairs: &BracketPairs,
+        is_code: &mut impl FnMut(usize) -> bool,
+    ) -> Option<(usize, usize)> {
+        let mut from = range.start;
+        if range.is_empty()
+            && let Some(ch) = buffer.chars_at(from).next()
+            && let Some((_, true)) = pairs.find(ch)
+        {
+            from += 1;
+        }
+        let mut depths = vec![0usize; pairs.0.len()];
+        let mut chars = buffer.chars_at(from);
+        let mut idx = from;
+        while let Some(ch) = chars.prev() {
+            idx -= 1;
+            if from - idx > MAX_DISTANCE {
+                return None;
+            }
+            let Some((pair, opens)) = pairs.find(ch) else {
+                continue;
+            };
+            if !is_code(idx) {
+                continue;
+            }
+            let depth = &mut depths[pairs.0.iter().position(|p| *p == pair)?];
+            if !opens {
+                *depth += 1;
+            } else if *depth > 0 {
+                *depth -= 1;
+            } else if let Some(close) = Self::scan_forward(buffer, idx + 1, pair, is_code)
+                && close >= range.end
+            {
+                return Some((idx, close));
+            }
+        }
+        None
+    }
+
+    fn scan_forward(
+        buffer: &RopeBuffer,
+        from: usize,
+        (open, close): (char, char),
+        counts: &mut impl FnMut(usize) -> bool,
+    ) -> Option<usize> {
+        let mut depth = 0;
+        for (offset, ch) in buffer.chars_at(from).enumerate().take(MAX_DISTANCE) {
+            let idx = from + offset;
+            if (ch != open && ch != close) || !counts(idx) {
+                continue;
+            }
+            if ch == open {
+                depth += 1;
+            } else if depth == 0 {
+                return Some(idx);
+            } else {
+                depth -= 1;
+            }
+        }
+        None
+    }
+
+    fn scan_backward(
+        buffer: &RopeBuffer,
+        from: usize,
+        (open, close): (char, char),
+        counts: &mut impl FnMut(usize) -> bool,
+    ) -> Option<usize> {
+        let mut depth = 0;
+        let mut chars = buffer.chars_at(from);
+        let mut idx = from;
+        while let Some(ch) = chars.prev() {
+            idx -= 1;
+            if from - idx > MAX_DISTANCE {
+                break;
+            }
+            if (ch != open && ch != close) || !counts(idx) {
+                continue;
+            }
+            if ch == close {
+                depth += 1;
+            } else if depth == 0 {
+                return Some(idx);
+            } else {
+                depth -= 1;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_code(_: usize) -> bool {
+        true
+    }
+
+    /// Treats everything between double quotes, quotes included, as a string.
+    fn code_map(text: &str) -> Vec<bool> {
+        let mut in_string = false;
+        text.chars()
+            .map(|ch| {
+                if ch == '"' {
+                    in_string = !in_string;
+                    return false;
+                }
+                !in_string
+            })
+            .collect()
+    }
+
+    fn find_match(text: &str, idx: usize) -> Option<(usize, usize)> {
+        let buffer = RopeBuffer::from_string(text);
+        let code = code_map(text);
+        BracketFeature::find_match(&buffer, idx, &BracketPairs::default(), &mut |i| code[i])
+    }
+
+    fn enclosing(text: &str, range: Range<usize>) -> Option<(usize, usize)> {
+        let buffer = RopeBuffer::from_string(text);
+        let code = code_map(text);
+        BracketFeature::enclosing(&buffer, range, &BracketPairs::default(), &mut |i| code[i])
+    }
+
+    #[test]
+    fn parse_rejects_odd_and_repeated_brackets() {
+        assert!(BracketPairs::parse("()[]").is_ok());
+        assert!(BracketPairs::parse("()[").is_err());
+        assert!(BracketPairs::parse("(()").is_err());
+        assert_eq!(BracketPairs::parse("«»").unwrap().closer('«'), Some('»'));
+    }
+
+    #[test]
+    fn find_match_skips_nested_pairs_in_both_directions() {
+        let text = "f(a(b)[c])";
+        assert_eq!(find_match(text, 1), Some((1, 9)));
+        assert_eq!(find_match(text, 9), Some((9, 1)));
+        assert_eq!(find_match(text, 3), Some((3, 5)));
+        assert_eq!(find_match(text, 6), Some((6, 8)));
+    }
+
+    #[test]
+    fn find_match_falls_back_to_the_bracket_before_the_cursor() {
+        assert_eq!(find_match("(a) b", 3), Some((2, 0)));
+        assert_eq!(find_match("(a) b", 4), None);
+    }
+
+    #[test]
+    fn unmatched_brackets_have_no_partner() {
+        assert_eq!(find_match("(a(b)", 0), None);
+        assert_eq!(find_match("a)b)", 3), None);
+        assert_eq!(find_match("(]", 0), None);
+    }
+
+    #[test]
+    fn brackets_in_strings_only_match_within_strings() {
+        let text = r#"(")" x)"#;
+        assert_eq!(find_match(text, 0), Some((0, 6)));
+        assert_eq!(find_match(text, 6), Some((6, 0)));
+        let text = r#""(" ( ")""#;
+        assert_eq!(find_match(text, 1), Some((1, 7)));
+        assert_eq!(find_match(text, 4), None);
+    }
+
+    #[test]
+    fn enclosing_finds_the_innermost_pair() {
+        let text = "{ a(b, [c]) }";
+        assert_eq!(enclosing(text, 8..8), Some((7, 9)));
+        assert_eq!(enclosing(text, 5..5), Some((3, 10)));
+        assert_eq!(enclosing(text, 1..1), Some((0, 12)));
+        assert_eq!(enclosing(text, 4..9), Some((3, 10)));
+        assert_eq!(enclosing(text, 2..11), Some((0, 12)));
+        assert_eq!(enclosing("a (b)", 0..0), None);
+    }
+
+    #[test]
+    fn enclosing_an_empty_range_at_an_opener_uses_that_pair() {
+        let text = "(a [b])";
+        assert_eq!(enclosing(text, 3..3), Some((3, 5)));
+        assert_eq!(enclosing(text, 3..4), Some((0, 6)));
+    }
+
+    #[test]
+    fn enclosing_ignores_brackets_in_strings_and_unclosed_openers() {
+        assert_eq!(enclosing(r#"(a ")" b)"#, 7..7), Some((0, 8)));
+        assert_eq!(enclosing("(a [b c)", 6..6), Some((0, 7)));
+        let buffer = RopeBuffer::from_string("(a)");
+        let pairs = BracketPairs::default();
+        assert_eq!(BracketFeature::enclosing(&buffer, 3..3, &pairs, &mut all_code), None);
+    }
+}
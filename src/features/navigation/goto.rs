@@ -1,4 +1,11 @@
+use crate::core::buffer::RopeBuffer;
+use crate::core::cursor::{CursorMovement, CursorPosition};
+
 pub struct GotoFeature;
 
 impl GotoFeature {
-}
\ No newline at end of file
+    pub fn goto(cursor: &mut CursorPosition, buffer: &RopeBuffer, line: usize, column: usize) {
+        cursor.move_to(line, column);
+        CursorMovement::clamp(cursor, buffer);
+    }
+}
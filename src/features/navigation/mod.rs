@@ -1,6 +1,7 @@
-
+mod brackets;
 mod goto;
 mod search;
 
+pub use brackets::{BracketFeature, BracketPairs};
 pub use goto::GotoFeature;
-pub use search::SearchFeature;
\ No newline at end of file
+pub use search::SearchFeature;
@@ -1,4 +1,81 @@
+use crate::core::buffer::RopeBuffer;
+use crate::core::cursor::CursorPosition;
+use std::borrow::Cow;
+use std::ops::Range;
+
 pub struct SearchFeature;
 
 impl SearchFeature {
-}
\ No newline at end of file
+    /// Finds the next match of `query` after (or before) `from`, wrapping
+    /// around the buffer. Lowercase queries match case-insensitively.
+    pub fn find(
+        buffer: &RopeBuffer,
+        from: &CursorPosition,
+        query: &str,
+        forward: bool,
+    ) -> Option<CursorPosition> {
+        if query.is_empty() {
+            return None;
+        }
+        let ignore_case = !query.chars().any(char::is_uppercase);
+        let query = if ignore_case {
+            query.to_lowercase()
+        } else {
+            query.to_string()
+        };
+        let total = buffer.len_lines();
+
+        for step in 0..=total {
+            let line_idx = if forward {
+                (from.line + step) % total
+            } else {
+                (from.line + total - step % total) % total
+            };
+            let Some(line) = buffer.line(line_idx) else {
+                continue;
+            };
+            let line = Cow::from(line);
+            let line = if ignore_case {
+                Cow::Owned(line.to_lowercase())
+            } else {
+                line
+            };
+            let columns = match_columns(&line, &query);
+
+            let found = match (step, forward) {
+                (0, true) => columns.into_iter().find(|c| *c > from.column),
+                (0, false) => columns.into_iter().rev().find(|c| *c < from.column),
+                (s, true) if s == total => columns.into_iter().find(|c| *c <= from.column),
+                (s, false) if s == total => columns.into_iter().rev().find(|c| *c >= from.column),
+                (_, true) => columns.into_iter().next(),
+                (_, false) => columns.into_iter().next_back(),
+            };
+            if let Some(column) = found {
+                return Some(CursorPosition::at(line_idx, column));
+            }
+        }
+        None
+    }
+
+    pub fn line_matches(line: &str, query: &str) -> Vec<Range<usize>> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let (line, query) = if query.chars().any(char::is_uppercase) {
+            (Cow::Borrowed(line), Cow::Borrowed(query))
+        } else {
+            (Cow::Owned(line.to_lowercase()), Cow::Owned(query.to_lowercase()))
+        };
+        let len = query.chars().count();
+        match_columns(&line, &query)
+            .into_iter()
+            .map(|column| column..column + len)
+            .collect()
+    }
+}
+
+fn match_columns(line: &str, query: &str) -> Vec<usize> {
+    line.match_indices(query)
+        .map(|(byte, _)| line[..byte].chars().count())
+        .collect()
+}
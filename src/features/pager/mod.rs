@@ -0,0 +1,3 @@
+mod source;
+
+pub use source::{PagerSource, PagerState};
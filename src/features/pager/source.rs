@@ -0,0 +1,86 @@
+use crate::app::TaskSpawner;
+use crate::core::cursor::CursorMovement;
+use crate::state::EditorState;
+use crate::utils::text;
+use std::io::Read;
+use std::thread;
+
+const READ_CHUNK: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PagerState {
+    pub loading: bool,
+    pub follow: bool,
+    pub bytes_read: u64,
+    pub error: Option<String>,
+}
+
+impl PagerState {
+    pub fn new() -> Self {
+        Self {
+            loading: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Streams a reader (usually piped stdin) into the document in chunks, so the
+/// first screen shows while the producer is still writing.
+pub struct PagerSource;
+
+impl PagerSource {
+    pub fn spawn<R: Read + Send + 'static>(reader: R, tasks: &TaskSpawner) {
+        let tasks = tasks.clone();
+        thread::spawn(move || {
+            let streamed = text::stream_utf8(reader, READ_CHUNK, |text, n| {
+                tasks.update(move |state| Self::append(state, &text, n))
+            });
+            if let Some((rest, result)) = streamed {
+                let error = result.err().map(|e| e.to_string());
+                tasks.update(move |state| Self::finish(state, &rest, error));
+            }
+        });
+    }
+
+    fn append(state: &mut EditorState, text: &str, bytes: usize) {
+        let Some(pager) = state.pager_mut() else {
+            return;
+        };
+        pager.bytes_read += bytes as u64;
+        let follow = pager.follow;
+
+        let document = state.document_mut();
+        document.load_buffer().append(text);
+        // Captured output only counts as unsaved once it has been saved somewhere.
+        if document.file_path().is_some() {
+            document.mark_modified();
+        }
+
+        if follow {
+            Self::scroll_to_end(state);
+        }
+    }
+
+    fn finish(state: &mut EditorState, rest: &str, error: Option<String>) {
+        if !rest.is_empty() {
+            Self::append(state, rest, 0);
+        }
+        let Some(pager) = state.pager_mut() else {
+            return;
+        };
+        pager.loading = false;
+        pager.error = error;
+        let message = match &pager.error {
+            Some(e) => format!("Input ended with an error: {}", e),
+            None => format!("Input complete: {} bytes", pager.bytes_read),
+        };
+        state.message_mut().set(message);
+    }
+
+    pub fn scroll_to_end(state: &mut EditorState) {
+        let (buffer, cursor, viewport) = state.buffer_cursor_viewport();
+        cursor.line = buffer.len_lines().saturating_sub(1);
+        CursorMovement::clamp(cursor, buffer);
+        viewport.adjust_for_cursor(cursor.line);
+    }
+}
@@ -0,0 +1,9 @@
+mod positions;
+mod recent;
+#[allow(clippy::module_inception)]
+mod session;
+mod store;
+
+pub use positions::{FilePosition, FilePositions};
+pub use recent::{RecentFiles, RecentList};
+pub use session::{Session, SessionFile};
@@ -0,0 +1,95 @@
+use crate::core::cursor::CursorPosition;
+use crate::core::document::Document;
+use crate::features::navigation::GotoFeature;
+use crate::state::EditorState;
+use super::store::{StateFile, format_entry, parse_entry};
+use crate::utils::paths;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const POSITIONS_MAGIC: &str = "KAPPA-POSITIONS 1";
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilePosition {
+    pub line: usize,
+    pub column: usize,
+    pub scroll: usize,
+}
+
+pub struct FilePositions;
+
+impl FilePositions {
+    pub fn lookup(file_path: &Path) -> Option<FilePosition> {
+        let file_path = paths::absolute(file_path);
+        Self::load()
+            .into_iter()
+            .find(|(path, _)| *path == file_path)
+            .map(|(_, position)| position)
+    }
+
+    pub fn record(document: &Document, cursor: &CursorPosition, scroll: usize) -> io::Result<()> {
+        let Some(file_path) = document.file_path() else {
+            return Ok(());
+        };
+        let Some(file) = Self::state_file() else {
+            return Ok(());
+        };
+        let file_path = paths::absolute(file_path);
+        let position = FilePosition {
+            line: cursor.line,
+            column: cursor.column,
+            scroll,
+        };
+
+        let mut entries = Self::load();
+        entries.retain(|(path, _)| *path != file_path);
+        entries.insert(0, (file_path, position));
+        entries.truncate(MAX_ENTRIES);
+
+        let mut out = String::new();
+        for (path, p) in &entries {
+            out.push_str(&format_entry(path, p));
+            out.push('\n');
+        }
+        file.write(&out)
+    }
+
+    /// Records where the current document is. One still loading has not been
+    /// placed yet, so its remembered position is kept.
+    pub fn record_current(state: &EditorState) -> io::Result<()> {
+        if state.loading().is_some() {
+            return Ok(());
+        }
+        Self::record(state.document(), state.cursor(), state.viewport().scroll_offset())
+    }
+
+    pub fn restore(state: &mut EditorState) {
+        let position = state.document().file_path().and_then(|p| Self::lookup(p));
+        if let Some(position) = position {
+            Self::apply(state, position);
+        }
+    }
+
+    pub fn apply(state: &mut EditorState, position: FilePosition) {
+        if let Some(progress) = state.loading_mut() {
+            progress.position = Some((position.line, position.column));
+            return;
+        }
+        let (buffer, cursor, viewport) = state.buffer_cursor_viewport();
+        GotoFeature::goto(cursor, buffer, position.line, position.column);
+        viewport.set_scroll_offset(position.scroll.min(cursor.line));
+        viewport.adjust_for_cursor(cursor.line);
+    }
+
+    fn state_file() -> Option<StateFile> {
+        StateFile::new("positions", POSITIONS_MAGIC)
+    }
+
+    fn load() -> Vec<(PathBuf, FilePosition)> {
+        let Some(body) = Self::state_file().and_then(|f| f.read().ok().flatten()) else {
+            return Vec::new();
+        };
+        body.lines().filter_map(parse_entry).collect()
+    }
+}
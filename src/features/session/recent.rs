@@ -0,0 +1,163 @@
+use crate::features::finder::{FinderResult, FuzzyMatcher};
+use super::store::StateFile;
+use crate::utils::paths;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const RECENT_MAGIC: &str = "KAPPA-RECENT 1";
+const MAX_ENTRIES: usize = 100;
+
+pub struct RecentFiles;
+
+impl RecentFiles {
+    pub fn list() -> Vec<PathBuf> {
+        let entries = Self::load();
+        let existing: Vec<PathBuf> = entries.iter().filter(|p| p.is_file()).cloned().collect();
+        if existing.len() != entries.len() {
+            let _ = Self::store(&existing);
+        }
+        existing
+    }
+
+    pub fn record(file_path: &Path) -> io::Result<()> {
+        let file_path = paths::absolute(file_path);
+        let mut entries = Self::load();
+        entries.retain(|p| *p != file_path);
+        entries.insert(0, file_path);
+        entries.truncate(MAX_ENTRIES);
+        Self::store(&entries)
+    }
+
+    pub fn remove(file_path: &Path) -> io::Result<()> {
+        let mut entries = Self::load();
+        entries.retain(|p| p != file_path);
+        Self::store(&entries)
+    }
+
+    fn state_file() -> Option<StateFile> {
+        StateFile::new("recent", RECENT_MAGIC)
+    }
+
+    fn load() -> Vec<PathBuf> {
+        let Some(body) = Self::state_file().and_then(|f| f.read().ok().flatten()) else {
+            return Vec::new();
+        };
+        body.lines().filter(|l| !l.is_empty()).map(PathBuf::from).collect()
+    }
+
+    fn store(entries: &[PathBuf]) -> io::Result<()> {
+        let Some(file) = Self::state_file() else {
+            return Ok(());
+        };
+        let mut out = String::new();
+        for path in entries {
+            out.push_str(&format!("{}\n", path.display()));
+        }
+        file.write(&out)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RecentList {
+    paths: Vec<PathBuf>,
+    labels: Vec<String>,
+    results: Vec<FinderResult>,
+    selected: usize,
+}
+
+impl RecentList {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let home = paths::home_dir();
+        let labels = paths
+            .iter()
+            .map(|p| match home.as_ref().and_then(|h| p.strip_prefix(h).ok()) {
+                Some(rest) => format!("~/{}", rest.display()),
+                None => p.display().to_string(),
+            })
+            .collect();
+        let mut list = Self {
+            paths,
+            labels,
+            results: Vec::new(),
+            selected: 0,
+        };
+        list.set_query("");
+        list
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        self.results = self
+            .labels
+            .iter()
+            .enumerate()
+            .filter_map(|(index, label)| {
+                if query.is_empty() {
+                    return Some(FinderResult {
+                        index,
+                        score: 0,
+                        positions: Vec::new(),
+                    });
+                }
+                FuzzyMatcher::score(query, label).map(|m| FinderResult {
+                    index,
+                    score: m.score,
+                    positions: m.positions,
+                })
+            })
+            .collect();
+        // Ties keep most-recent-first order.
+        self.results
+            .sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.index.cmp(&b.index)));
+        self.selected = 0;
+    }
+
+    pub fn results(&self) -> &[FinderResult] {
+        &self.results
+    }
+
+    pub fn label(&self, result: &FinderResult) -> &str {
+        &self.labels[result.index]
+    }
+
+    pub fn total(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.results.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn selected_path(&self) -> Option<&Path> {
+        self.results
+            .get(self.selected)
+            .map(|r| self.paths[r.index].as_path())
+    }
+
+    pub fn remove_selected(&mut self) -> Option<PathBuf> {
+        let result = self.results.get(self.selected)?.clone();
+        let path = self.paths.remove(result.index);
+        self.labels.remove(result.index);
+        self.results.remove(self.selected);
+        for r in &mut self.results {
+            if r.index > result.index {
+                r.index -= 1;
+            }
+        }
+        self.selected = self.selected.min(self.results.len().saturating_sub(1));
+        Some(path)
+    }
+}
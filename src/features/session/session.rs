@@ -0,0 +1,161 @@
+use super::positions::{FilePosition, FilePositions};
+use super::store::{StateFile, format_entry, parse_entry};
+use crate::features::file_operations::{ArgFile, ArgList, OpenFile};
+use crate::state::EditorState;
+use crate::utils::paths;
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const SESSION_MAGIC: &str = "KAPPA-SESSION 1";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionFile {
+    pub path: PathBuf,
+    pub position: FilePosition,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub files: Vec<SessionFile>,
+    pub current: usize,
+    pub explorer_visible: bool,
+}
+
+impl Session {
+    fn state_file(dir: &Path) -> Option<StateFile> {
+        let name = Path::new("sessions").join(paths::mangle(dir));
+        StateFile::new(name, SESSION_MAGIC)
+    }
+
+    /// Captures the command-line files plus the current document; `None` when
+    /// nothing is backed by a file.
+    pub fn capture(state: &EditorState) -> Option<Self> {
+        let current_path = state.document().file_path().map(|p| paths::absolute(p));
+        let mut paths: Vec<PathBuf> = state
+            .arg_list()
+            .files()
+            .iter()
+            .map(|f| paths::absolute(&f.path))
+            .collect();
+        if let Some(current) = &current_path
+            && !paths.contains(current)
+        {
+            paths.push(current.clone());
+        }
+        if paths.is_empty() {
+            return None;
+        }
+
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let position = if Some(&path) == current_path.as_ref() {
+                    FilePosition {
+                        line: state.cursor().line,
+                        column: state.cursor().column,
+                        scroll: state.viewport().scroll_offset(),
+                    }
+                } else {
+                    FilePositions::lookup(&path).unwrap_or(FilePosition {
+                        line: 0,
+                        column: 0,
+                        scroll: 0,
+                    })
+                };
+                SessionFile { path, position }
+            })
+            .collect::<Vec<_>>();
+        let current = current_path
+            .and_then(|c| files.iter().position(|f| f.path == c))
+            .unwrap_or(0);
+
+        Some(Self {
+            files,
+            current,
+            explorer_visible: state.is_explorer_visible(),
+        })
+    }
+
+    pub fn save(&self, dir: &Path) -> io::Result<()> {
+        let Some(state_file) = Self::state_file(dir) else {
+            return Ok(());
+        };
+        let mut out = format!("current\t{}\n", self.current);
+        out.push_str(&format!("explorer\t{}\n", self.explorer_visible as u8));
+        for file in &self.files {
+            out.push_str(&format!("file\t{}\n", format_entry(&file.path, &file.position)));
+        }
+        state_file.write(&out)
+    }
+
+    pub fn load(dir: &Path) -> io::Result<Option<Self>> {
+        let Some(body) = Self::state_file(dir).map(|f| f.read()).transpose()?.flatten() else {
+            return Ok(None);
+        };
+
+        let mut session = Self {
+            files: Vec::new(),
+            current: 0,
+            explorer_visible: false,
+        };
+        for line in body.lines() {
+            match line.split_once('\t') {
+                Some(("current", value)) => session.current = value.parse().unwrap_or(0),
+                Some(("explorer", value)) => session.explorer_visible = value == "1",
+                Some(("file", entry)) => {
+                    if let Some((path, position)) = parse_entry(entry) {
+                        session.files.push(SessionFile { path, position });
+                    }
+                }
+                _ => {}
+            }
+        }
+        session.current = session.current.min(session.files.len().saturating_sub(1));
+        Ok(Some(session))
+    }
+
+    pub fn save_current(state: &EditorState) -> io::Result<()> {
+        match (Self::capture(state), env::current_dir()) {
+            (Some(session), Ok(dir)) => session.save(&dir),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn current_file(&self) -> Option<&SessionFile> {
+        self.files.get(self.current)
+    }
+
+    /// Reopens the session's files and layout, replacing the current document
+    /// without asking; callers confirm unsaved changes first.
+    pub fn restore(&self, state: &mut EditorState) -> io::Result<()> {
+        let files = self
+            .files
+            .iter()
+            .map(|f| ArgFile {
+                path: f.path.clone(),
+                position: None,
+            })
+            .collect();
+        state.set_arg_list(ArgList::new(files, false));
+        if self.explorer_visible
+            && let Ok(dir) = env::current_dir()
+        {
+            state.explorer_mut().set_root(dir);
+            state.set_explorer_visible(true);
+        }
+
+        let Some(file) = self.current_file() else {
+            return Ok(());
+        };
+        let tasks = state.tasks().cloned();
+        let path = file.path.to_string_lossy();
+        let opened = OpenFile::open_with(&path, state.large_files(), tasks.as_ref())?;
+        match opened.loading {
+            Some(progress) => state.start_loading(opened.document, progress),
+            None => state.replace_document(opened.document),
+        }
+        FilePositions::apply(state, file.position);
+        Ok(())
+    }
+}
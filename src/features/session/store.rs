@@ -0,0 +1,120 @@
+use super::FilePosition;
+use crate::utils::paths;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A text file under the state directory whose first line is `magic`.
+pub(super) struct StateFile {
+    path: PathBuf,
+    magic: &'static str,
+}
+
+impl StateFile {
+    pub(super) fn new(name: impl AsRef<Path>, magic: &'static str) -> Option<Self> {
+        let path = paths::state_dir()?.join(name);
+        Some(Self { path, magic })
+    }
+
+    /// The text after the header; `None` if the file does not exist.
+    pub(super) fn read(&self) -> io::Result<Option<String>> {
+        let raw = match fs::read_to_string(&self.path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        match raw.split_once('\n') {
+            Some((header, body)) if header == self.magic => Ok(Some(body.to_string())),
+            None if raw == self.magic => Ok(Some(String::new())),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} has an unknown format", self.path.display()),
+            )),
+        }
+    }
+
+    /// Replaces the file through a temporary one, so readers never see it
+    /// half written.
+    pub(super) fn write(&self, body: &str) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut temp_name = self.path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp_path = self.path.with_file_name(temp_name);
+        let written = fs::write(&temp_path, format!("{}\n{}", self.magic, body))
+            .and_then(|_| fs::rename(&temp_path, &self.path));
+        if written.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        written
+    }
+}
+
+pub(super) fn format_entry(path: &Path, p: &FilePosition) -> String {
+    format!("{}\t{}\t{}\t{}", p.line, p.column, p.scroll, path.display())
+}
+
+pub(super) fn parse_entry(line: &str) -> Option<(PathBuf, FilePosition)> {
+    let mut fields = line.splitn(4, '\t');
+    let line_idx = fields.next()?.parse().ok()?;
+    let column = fields.next()?.parse().ok()?;
+    let scroll = fields.next()?.parse().ok()?;
+    let path = PathBuf::from(fields.next()?);
+    Some((
+        path,
+        FilePosition {
+            line: line_idx,
+            column,
+            scroll,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> StateFile {
+        let dir = std::env::temp_dir().join(format!("kappa-store-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        StateFile {
+            path: dir.join("nested").join("state"),
+            magic: "KAPPA-TEST 1",
+        }
+    }
+
+    #[test]
+    fn write_then_read_round_trips_the_body() {
+        let file = temp_file("round-trip");
+        assert_eq!(file.read().unwrap(), None);
+        file.write("a\nb\n").unwrap();
+        assert_eq!(file.read().unwrap().as_deref(), Some("a\nb\n"));
+        file.write("").unwrap();
+        assert_eq!(file.read().unwrap().as_deref(), Some(""));
+        let _ = fs::remove_dir_all(file.path.parent().unwrap().parent().unwrap());
+    }
+
+    #[test]
+    fn read_rejects_a_different_header() {
+        let file = temp_file("bad-header");
+        fs::create_dir_all(file.path.parent().unwrap()).unwrap();
+        fs::write(&file.path, "KAPPA-TEST 2\na\n").unwrap();
+        let err = file.read().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let _ = fs::remove_dir_all(file.path.parent().unwrap().parent().unwrap());
+    }
+
+    #[test]
+    fn entries_round_trip_paths_with_tabs() {
+        let position = FilePosition {
+            line: 3,
+            column: 7,
+            scroll: 1,
+        };
+        let path = Path::new("/tmp/a\tb.rs");
+        let entry = format_entry(path, &position);
+        assert_eq!(parse_entry(&entry), Some((path.to_path_buf(), position)));
+        assert_eq!(parse_entry("3\tx\t1\t/tmp/a"), None);
+    }
+}
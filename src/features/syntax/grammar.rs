@@ -0,0 +1,112 @@
+use super::languages::GRAMMARS;
+use std::path::Path;
+
+#[derive(Debug)]
+pub struct Grammar {
+    pub name: &'static str,
+    pub extensions: &'static [&'static str],
+    pub file_names: &'static [&'static str],
+    /// Interpreters named on a `#!` line, without version suffixes.
+    pub interpreters: &'static [&'static str],
+    pub words: &'static [(&'static str, &'static [&'static str])],
+    /// Tried in order, so longer delimiters must come first.
+    pub regions: &'static [Region],
+    pub line_prefixes: &'static [LinePrefix],
+    pub numbers: bool,
+    pub functions: bool,
+    pub macros: bool,
+    pub capitalized_types: bool,
+    /// Rust-style `'a` lifetimes, told apart from `'a'` characters.
+    pub lifetimes: bool,
+    pub variable_sigil: Option<char>,
+    pub key_separator: Option<char>,
+    pub indent_after: &'static [&'static str],
+    pub line_comment: Option<&'static str>,
+    pub block_comment: Option<(&'static str, &'static str)>,
+}
+
+#[derive(Debug)]
+pub struct Region {
+    pub open: &'static str,
+    /// Empty for regions that run to the end of the line.
+    pub close: &'static str,
+    pub scope: &'static str,
+    pub escape: Option<char>,
+    pub multiline: bool,
+    /// Whether `open` inside the region nests, as in Rust block comments.
+    pub nested: bool,
+}
+
+#[derive(Debug)]
+pub struct LinePrefix {
+    pub prefix: &'static str,
+    pub scope: &'static str,
+    pub whole_line: bool,
+}
+
+impl Region {
+    pub const fn new(open: &'static str, close: &'static str, scope: &'static str) -> Self {
+        Self {
+            open,
+            close,
+            scope,
+            escape: None,
+            multiline: false,
+            nested: false,
+        }
+    }
+
+    pub const fn escaped(self) -> Self {
+        Self {
+            escape: Some('\\'),
+            ..self
+        }
+    }
+
+    pub const fn multiline(self) -> Self {
+        Self {
+            multiline: true,
+            ..self
+        }
+    }
+
+    pub const fn nested(self) -> Self {
+        Self {
+            nested: true,
+            ..self
+        }
+    }
+}
+
+impl Grammar {
+    pub fn detect(path: Option<&Path>, first_line: &str) -> Option<&'static Grammar> {
+        if let Some(path) = path {
+            let file_name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let extension = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            let found = GRAMMARS.iter().find(|g| {
+                g.file_names.contains(&file_name.as_str())
+                    || (!extension.is_empty() && g.extensions.contains(&extension.as_str()))
+            });
+            if found.is_some() {
+                return found;
+            }
+        }
+        let interpreter = shebang_interpreter(first_line)?;
+        GRAMMARS.iter().find(|g| g.interpreters.contains(&interpreter))
+    }
+}
+
+fn shebang_interpreter(line: &str) -> Option<&str> {
+    let mut words = line.strip_prefix("#!")?.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|w| !w.starts_with('-'))?;
+    }
+    Some(program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.'))
+}
@@ -0,0 +1,99 @@
+use super::grammar::Grammar;
+use super::lexer::{self, LineState, Token};
+use crate::core::buffer::{BufferEdit, RopeBuffer};
+
+/// Longer lines (minified files, say) are left plain and do not change the
+/// state, so they cannot stall typing.
+const MAX_LINE_CHARS: usize = 10_000;
+
+/// Syntax highlighting for one document. Only the state at the start of
+/// each line is kept; visible lines are lexed again when drawn.
+#[derive(Debug)]
+pub struct Highlighter {
+    grammar: &'static Grammar,
+    states: Vec<LineState>,
+    /// `states[..valid]` are known to be correct.
+    valid: usize,
+    /// `states[carried..]` were computed before the last edits and shifted
+    /// into place; they become valid again once re-lexing reproduces one.
+    carried: usize,
+}
+
+impl Highlighter {
+    pub fn new(grammar: &'static Grammar) -> Self {
+        Self {
+            grammar,
+            states: vec![LineState::Normal],
+            valid: 1,
+            carried: 1,
+        }
+    }
+
+    pub fn grammar(&self) -> &'static Grammar {
+        self.grammar
+    }
+
+    pub fn edit(&mut self, edit: &BufferEdit) {
+        let (start_line, old_end_line, new_end_line) = (edit.start.0, edit.old_end.0, edit.new_end.0);
+        let first = start_line + 1;
+        if first < self.states.len() {
+            let removed_end = (old_end_line + 1).min(self.states.len());
+            let added = new_end_line - start_line;
+            self.states
+                .splice(first..removed_end, std::iter::repeat_n(LineState::Normal, added));
+        }
+        let delta = new_end_line as isize - old_end_line as isize;
+        let shifted = if self.carried > start_line {
+            self.carried.saturating_add_signed(delta)
+        } else {
+            self.carried
+        };
+        self.carried = shifted.max(new_end_line + 1);
+        self.valid = self.valid.min(first);
+    }
+
+    /// Lexes forward until the state of line `last` is known. Stops early
+    /// once a re-lexed state matches the one carried over from before an
+    /// edit, since everything after it is then unchanged.
+    pub fn update(&mut self, buffer: &RopeBuffer, last: usize) {
+        let last = last.min(buffer.len_lines().saturating_sub(1));
+        while self.valid <= last {
+            let line = self.valid - 1;
+            let next = match line_chars(buffer, line) {
+                Some(chars) => lexer::lex_line(self.grammar, &chars, self.states[line]).1,
+                None => self.states[line],
+            };
+            if self.valid < self.states.len() {
+                if self.valid >= self.carried && self.states[self.valid] == next {
+                    self.valid = self.states.len();
+                    break;
+                }
+                self.states[self.valid] = next;
+            } else {
+                self.states.push(next);
+            }
+            self.valid += 1;
+        }
+        if self.valid >= self.states.len() {
+            self.carried = self.states.len();
+        }
+    }
+
+    pub fn line_tokens(&self, buffer: &RopeBuffer, line_idx: usize) -> Vec<Token> {
+        if line_idx >= self.valid {
+            return Vec::new();
+        }
+        match line_chars(buffer, line_idx) {
+            Some(chars) => lexer::lex_line(self.grammar, &chars, self.states[line_idx]).0,
+            None => Vec::new(),
+        }
+    }
+}
+
+fn line_chars(buffer: &RopeBuffer, line_idx: usize) -> Option<Vec<char>> {
+    let len = buffer.line_len(line_idx);
+    if len > MAX_LINE_CHARS {
+        return None;
+    }
+    Some(buffer.line(line_idx)?.chars().take(len).collect())
+}
@@ -0,0 +1,235 @@
+use super::grammar::{Grammar, LinePrefix, Region};
+
+pub static GRAMMARS: &[Grammar] = &[RUST, TOML, MARKDOWN, JSON, PYTHON, SHELL, C];
+
+const PLAIN: Grammar = Grammar {
+    name: "",
+    extensions: &[],
+    file_names: &[],
+    interpreters: &[],
+    words: &[],
+    regions: &[],
+    line_prefixes: &[],
+    numbers: true,
+    functions: false,
+    macros: false,
+    capitalized_types: false,
+    lifetimes: false,
+    variable_sigil: None,
+    key_separator: None,
+    indent_after: &[],
+    line_comment: None,
+    block_comment: None,
+};
+
+const RUST: Grammar = Grammar {
+    name: "Rust",
+    extensions: &["rs"],
+    words: &[
+        (
+            "keyword",
+            &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod",
+                "move", "mut", "pub", "ref", "return", "static", "struct", "super", "trait",
+                "type", "unsafe", "use", "where", "while", "yield",
+            ],
+        ),
+        (
+            "type",
+            &[
+                "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16",
+                "i32", "i64", "i128", "isize", "f32", "f64", "Self",
+            ],
+        ),
+        ("constant", &["true", "false"]),
+        ("variable.builtin", &["self"]),
+    ],
+    regions: &[
+        Region::new("/*", "*/", "comment").multiline().nested(),
+        Region::new("//", "", "comment"),
+        Region::new("r#\"", "\"#", "string").multiline(),
+        Region::new("r\"", "\"", "string").multiline(),
+        Region::new("b\"", "\"", "string").escaped().multiline(),
+        Region::new("\"", "\"", "string").escaped().multiline(),
+        Region::new("b'", "'", "string").escaped(),
+        Region::new("'", "'", "string").escaped(),
+    ],
+    line_prefixes: &[
+        LinePrefix { prefix: "#[", scope: "attribute", whole_line: true },
+        LinePrefix { prefix: "#![", scope: "attribute", whole_line: true },
+    ],
+    functions: true,
+    macros: true,
+    capitalized_types: true,
+    lifetimes: true,
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+    ..PLAIN
+};
+
+const TOML: Grammar = Grammar {
+    name: "TOML",
+    extensions: &["toml"],
+    file_names: &["Cargo.lock"],
+    words: &[("constant", &["true", "false", "inf", "nan"])],
+    regions: &[
+        Region::new("#", "", "comment"),
+        Region::new("\"\"\"", "\"\"\"", "string").escaped().multiline(),
+        Region::new("'''", "'''", "string").multiline(),
+        Region::new("\"", "\"", "string").escaped(),
+        Region::new("'", "'", "string"),
+    ],
+    line_prefixes: &[LinePrefix { prefix: "[", scope: "type", whole_line: true }],
+    key_separator: Some('='),
+    line_comment: Some("#"),
+    ..PLAIN
+};
+
+const MARKDOWN: Grammar = Grammar {
+    name: "Markdown",
+    extensions: &["md", "markdown"],
+    regions: &[
+        Region::new("```", "```", "markup.raw").multiline(),
+        Region::new("`", "`", "markup.raw"),
+        Region::new("<!--", "-->", "comment").multiline(),
+        Region::new("**", "**", "markup.bold"),
+        Region::new("](", ")", "markup.link"),
+    ],
+    line_prefixes: &[
+        LinePrefix { prefix: "#", scope: "markup.heading", whole_line: true },
+        LinePrefix { prefix: ">", scope: "markup.quote", whole_line: true },
+        LinePrefix { prefix: "- ", scope: "markup.list", whole_line: false },
+        LinePrefix { prefix: "* ", scope: "markup.list", whole_line: false },
+        LinePrefix { prefix: "+ ", scope: "markup.list", whole_line: false },
+    ],
+    numbers: false,
+    block_comment: Some(("<!--", "-->")),
+    ..PLAIN
+};
+
+const JSON: Grammar = Grammar {
+    name: "JSON",
+    extensions: &["json", "jsonc"],
+    words: &[("constant", &["true", "false", "null"])],
+    regions: &[
+        Region::new("\"", "\"", "string").escaped(),
+        Region::new("//", "", "comment"),
+        Region::new("/*", "*/", "comment").multiline(),
+    ],
+    // For JSON with comments, as in editor and compiler settings.
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+    ..PLAIN
+};
+
+const PYTHON: Grammar = Grammar {
+    name: "Python",
+    extensions: &["py", "pyi"],
+    interpreters: &["python"],
+    words: &[
+        (
+            "keyword",
+            &[
+                "and", "as", "assert", "async", "await", "break", "class", "continue", "def",
+                "del", "elif", "else", "except", "finally", "for", "from", "global", "if",
+                "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise",
+                "return", "try", "while", "with", "yield", "match", "case",
+            ],
+        ),
+        ("constant", &["True", "False", "None"]),
+        ("variable.builtin", &["self", "cls"]),
+        (
+            "type",
+            &["int", "float", "str", "bool", "bytes", "list", "dict", "set", "tuple", "object"],
+        ),
+    ],
+    regions: &[
+        Region::new("#", "", "comment"),
+        Region::new("\"\"\"", "\"\"\"", "string").escaped().multiline(),
+        Region::new("'''", "'''", "string").escaped().multiline(),
+        Region::new("f\"", "\"", "string").escaped(),
+        Region::new("f'", "'", "string").escaped(),
+        Region::new("r\"", "\"", "string"),
+        Region::new("r'", "'", "string"),
+        Region::new("b\"", "\"", "string").escaped(),
+        Region::new("b'", "'", "string").escaped(),
+        Region::new("\"", "\"", "string").escaped(),
+        Region::new("'", "'", "string").escaped(),
+    ],
+    line_prefixes: &[LinePrefix { prefix: "@", scope: "attribute", whole_line: true }],
+    functions: true,
+    capitalized_types: true,
+    indent_after: &[":"],
+    line_comment: Some("#"),
+    ..PLAIN
+};
+
+const SHELL: Grammar = Grammar {
+    name: "Shell",
+    extensions: &["sh", "bash", "zsh", "ksh"],
+    file_names: &[".bashrc", ".bash_profile", ".profile", ".zshrc", ".zprofile"],
+    interpreters: &["sh", "bash", "zsh", "dash", "ksh"],
+    words: &[
+        (
+            "keyword",
+            &[
+                "if", "then", "else", "elif", "fi", "for", "while", "until", "do", "done",
+                "case", "esac", "in", "function", "select", "return", "break", "continue",
+                "local", "export", "readonly", "declare", "unset", "shift", "exit",
+            ],
+        ),
+        (
+            "function.builtin",
+            &[
+                "echo", "printf", "read", "cd", "test", "source", "eval", "exec", "set", "trap",
+                "wait",
+            ],
+        ),
+    ],
+    regions: &[
+        Region::new("#", "", "comment"),
+        Region::new("\"", "\"", "string").escaped().multiline(),
+        Region::new("'", "'", "string").multiline(),
+        Region::new("`", "`", "string").escaped(),
+    ],
+    variable_sigil: Some('$'),
+    indent_after: &["then", "do", "else"],
+    line_comment: Some("#"),
+    ..PLAIN
+};
+
+const C: Grammar = Grammar {
+    name: "C",
+    extensions: &["c", "h"],
+    words: &[
+        (
+            "keyword",
+            &[
+                "auto", "break", "case", "const", "continue", "default", "do", "else", "enum",
+                "extern", "for", "goto", "if", "inline", "register", "restrict", "return",
+                "sizeof", "static", "struct", "switch", "typedef", "union", "volatile", "while",
+            ],
+        ),
+        (
+            "type",
+            &[
+                "void", "char", "short", "int", "long", "float", "double", "signed", "unsigned",
+                "bool", "size_t", "ssize_t", "int8_t", "int16_t", "int32_t", "int64_t",
+                "uint8_t", "uint16_t", "uint32_t", "uint64_t", "FILE",
+            ],
+        ),
+        ("constant", &["NULL", "true", "false"]),
+    ],
+    regions: &[
+        Region::new("/*", "*/", "comment").multiline(),
+        Region::new("//", "", "comment"),
+        Region::new("\"", "\"", "string").escaped(),
+        Region::new("'", "'", "string").escaped(),
+    ],
+    line_prefixes: &[LinePrefix { prefix: "#", scope: "macro", whole_line: true }],
+    functions: true,
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+    ..PLAIN
+};
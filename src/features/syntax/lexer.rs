@@ -0,0 +1,211 @@
+use super::grammar::{Grammar, Region};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineState {
+    #[default]
+    Normal,
+    Region { index: usize, depth: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub range: Range<usize>,
+    pub scope: &'static str,
+}
+
+/// Splits one line (without its ending) into tokens, starting in `state`.
+/// Returns the tokens and the state the next line starts in.
+pub fn lex_line(grammar: &Grammar, chars: &[char], state: LineState) -> (Vec<Token>, LineState) {
+    let mut tokens = Vec::new();
+
+    let mut i = 0;
+    if let LineState::Region { index, depth } = state
+        && let Some(region) = grammar.regions.get(index)
+    {
+        let (end, next) = scan_region(region, index, chars, 0, depth);
+        push(&mut tokens, 0..end, region.scope);
+        if next != LineState::Normal {
+            return (tokens, next);
+        }
+        i = end;
+    } else {
+        let indent = chars.iter().take_while(|c| c.is_whitespace()).count();
+        if let Some(prefix) = grammar
+            .line_prefixes
+            .iter()
+            .find(|p| starts_with(chars, indent, p.prefix))
+        {
+            if prefix.whole_line {
+                push(&mut tokens, indent..chars.len(), prefix.scope);
+                return (tokens, LineState::Normal);
+            }
+            i = indent + prefix.prefix.chars().count();
+            push(&mut tokens, indent..i, prefix.scope);
+        }
+    }
+
+    while i < chars.len() {
+        let ch = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if grammar.lifetimes
+            && ch == '\''
+            && next.is_some_and(is_word_start)
+            && chars.get(i + 2) != Some(&'\'')
+        {
+            let end = word_end(chars, i + 1);
+            push(&mut tokens, i..end, "lifetime");
+            i = end;
+            continue;
+        }
+
+        if let Some((index, region)) = grammar
+            .regions
+            .iter()
+            .enumerate()
+            .find(|(_, r)| starts_with(chars, i, r.open))
+        {
+            let start = i;
+            let open_len = region.open.chars().count();
+            let (end, next_state) = scan_region(region, index, chars, i + open_len, 1);
+            push(&mut tokens, start..end, region.scope);
+            if next_state != LineState::Normal {
+                return (tokens, next_state);
+            }
+            i = end;
+            continue;
+        }
+
+        if grammar.variable_sigil == Some(ch) && let Some(next) = next {
+            let end = match next {
+                '{' => chars[i..]
+                    .iter()
+                    .position(|&c| c == '}')
+                    .map_or(chars.len(), |p| i + p + 1),
+                c if is_word_start(c) => word_end(chars, i + 1),
+                c if c.is_ascii_digit() || "#@?$!*-".contains(c) => i + 2,
+                _ => i + 1,
+            };
+            push(&mut tokens, i..end, "variable");
+            i = end;
+            continue;
+        }
+
+        if grammar.numbers && ch.is_ascii_digit() {
+            let mut end = i + 1;
+            while end < chars.len() {
+                let c = chars[end];
+                let decimal_point = c == '.' && chars.get(end + 1).is_some_and(char::is_ascii_digit);
+                if !(c.is_ascii_alphanumeric() || c == '_' || decimal_point) {
+                    break;
+                }
+                end += 1;
+            }
+            push(&mut tokens, i..end, "number");
+            i = end;
+            continue;
+        }
+
+        if is_word_start(ch) {
+            let mut end = word_end(chars, i);
+            if let Some(scope) = word_scope(grammar, chars, i, end) {
+                if scope == "macro" {
+                    end += 1;
+                }
+                push(&mut tokens, i..end, scope);
+            }
+            i = end;
+            continue;
+        }
+
+        i += 1;
+    }
+    (tokens, LineState::Normal)
+}
+
+fn push(tokens: &mut Vec<Token>, range: Range<usize>, scope: &'static str) {
+    if range.start < range.end {
+        tokens.push(Token { range, scope });
+    }
+}
+
+fn word_scope(grammar: &Grammar, chars: &[char], start: usize, end: usize) -> Option<&'static str> {
+    let word: String = chars[start..end].iter().collect();
+    if let Some((scope, _)) = grammar.words.iter().find(|(_, words)| words.contains(&word.as_str())) {
+        return Some(scope);
+    }
+    let next = chars.get(end).copied();
+    let next_visible = chars[end..].iter().find(|c| !c.is_whitespace()).copied();
+    if grammar.macros && next == Some('!') {
+        return Some("macro");
+    }
+    if grammar.key_separator.is_some() && next_visible == grammar.key_separator {
+        return Some("property");
+    }
+    if grammar.functions && next_visible == Some('(') {
+        return Some("function");
+    }
+    if grammar.capitalized_types && word.starts_with(|c: char| c.is_uppercase()) {
+        return Some("type");
+    }
+    None
+}
+
+fn scan_region(
+    region: &Region,
+    index: usize,
+    chars: &[char],
+    from: usize,
+    depth: usize,
+) -> (usize, LineState) {
+    let open_len = region.open.chars().count();
+    let close_len = region.close.chars().count();
+    let mut depth = depth;
+    let mut i = from;
+    while i < chars.len() {
+        if region.escape == Some(chars[i]) {
+            i += 2;
+            continue;
+        }
+        if close_len > 0 && starts_with(chars, i, region.close) {
+            i += close_len;
+            depth -= 1;
+            if depth == 0 {
+                return (i, LineState::Normal);
+            }
+            continue;
+        }
+        if region.nested && starts_with(chars, i, region.open) {
+            i += open_len;
+            depth += 1;
+            continue;
+        }
+        i += 1;
+    }
+    let state = if region.multiline && close_len > 0 {
+        LineState::Region { index, depth }
+    } else {
+        LineState::Normal
+    };
+    (chars.len(), state)
+}
+
+fn starts_with(chars: &[char], at: usize, pattern: &str) -> bool {
+    !pattern.is_empty()
+        && pattern
+            .chars()
+            .enumerate()
+            .all(|(i, p)| chars.get(at + i) == Some(&p))
+}
+
+fn is_word_start(ch: char) -> bool {
+    ch.is_alphabetic() || ch == '_'
+}
+
+fn word_end(chars: &[char], start: usize) -> usize {
+    chars[start..]
+        .iter()
+        .position(|&c| !(c.is_alphanumeric() || c == '_'))
+        .map_or(chars.len(), |p| start + p)
+}
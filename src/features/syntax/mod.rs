@@ -0,0 +1,12 @@
+mod grammar;
+mod highlighter;
+mod languages;
+mod lexer;
+#[cfg(feature = "tree-sitter")]
+mod tree;
+
+pub use grammar::{Grammar, LinePrefix, Region};
+pub use highlighter::Highlighter;
+pub use lexer::{LineState, Token};
+#[cfg(feature = "tree-sitter")]
+pub use tree::SyntaxTree;
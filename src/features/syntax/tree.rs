@@ -0,0 +1,218 @@
+use super::grammar::Grammar;
+use super::lexer::Token;
+use crate::core::buffer::{BufferEdit, RopeBuffer};
+use std::iter;
+use std::ops::Range;
+use std::sync::OnceLock;
+use tree_sitter::{
+    InputEdit, Language, Node, Parser, Point, Query, QueryCursor, StreamingIterator, Tree,
+};
+use tree_sitter_language::LanguageFn;
+
+/// Capture names from the bundled queries, renamed to the scopes the
+/// lexer uses so themes style both the same way.
+const SCOPE_ALIASES: &[(&str, &str)] = &[
+    ("boolean", "constant.boolean"),
+    ("constructor", "type.constructor"),
+    ("escape", "string.escape"),
+    ("function.macro", "macro"),
+    ("string.special.key", "property"),
+    ("text.title", "markup.heading"),
+    ("text.literal", "markup.raw"),
+    ("text.uri", "markup.link.uri"),
+    ("text.reference", "markup.link.reference"),
+];
+
+/// A compiled-in parser and its highlight query, which is compiled on first
+/// use and shared by every document in that language.
+struct TreeGrammar {
+    name: &'static str,
+    language: LanguageFn,
+    highlights: &'static str,
+    query: OnceLock<Option<Query>>,
+}
+
+static TREE_GRAMMARS: [TreeGrammar; 4] = [
+    TreeGrammar::new("Rust", tree_sitter_rust::LANGUAGE, tree_sitter_rust::HIGHLIGHTS_QUERY),
+    TreeGrammar::new("TOML", tree_sitter_toml_ng::LANGUAGE, tree_sitter_toml_ng::HIGHLIGHTS_QUERY),
+    TreeGrammar::new("JSON", tree_sitter_json::LANGUAGE, tree_sitter_json::HIGHLIGHTS_QUERY),
+    TreeGrammar::new("Markdown", tree_sitter_md::LANGUAGE, tree_sitter_md::HIGHLIGHT_QUERY_BLOCK),
+];
+
+impl TreeGrammar {
+    const fn new(name: &'static str, language: LanguageFn, highlights: &'static str) -> Self {
+        Self {
+            name,
+            language,
+            highlights,
+            query: OnceLock::new(),
+        }
+    }
+}
+
+/// A tree-sitter syntax tree for one document, kept in step with its edits.
+/// Ranges in and out are char indices into the buffer.
+pub struct SyntaxTree {
+    parser: Parser,
+    tree: Option<Tree>,
+    query: &'static Query,
+    /// Edits have been applied to `tree` but it has not been reparsed.
+    stale: bool,
+}
+
+impl SyntaxTree {
+    pub fn new(grammar: &Grammar) -> Option<Self> {
+        let tree_grammar = TREE_GRAMMARS.iter().find(|g| g.name == grammar.name)?;
+        let language = Language::new(tree_grammar.language);
+        let query = tree_grammar
+            .query
+            .get_or_init(|| Query::new(&language, tree_grammar.highlights).ok())
+            .as_ref()?;
+        let mut parser = Parser::new();
+        parser.set_language(&language).ok()?;
+        Some(Self {
+            parser,
+            tree: None,
+            query,
+            stale: true,
+        })
+    }
+
+    pub fn edit(&mut self, edit: &BufferEdit) {
+        if let Some(tree) = &mut self.tree {
+            tree.edit(&InputEdit {
+                start_byte: edit.start_byte,
+                old_end_byte: edit.old_end_byte,
+                new_end_byte: edit.new_end_byte,
+                start_position: point(edit.start),
+                old_end_position: point(edit.old_end),
+                new_end_position: point(edit.new_end),
+            });
+        }
+        self.stale = true;
+    }
+
+    pub fn update(&mut self, buffer: &RopeBuffer) {
+        if !self.stale {
+            return;
+        }
+        let tree = self.parser.parse_with_options(
+            &mut |byte, _| buffer.chunk_at_byte(byte).as_bytes(),
+            self.tree.as_ref(),
+            None,
+        );
+        self.tree = tree;
+        self.stale = false;
+    }
+
+    pub fn line_tokens(&self, buffer: &RopeBuffer, line_idx: usize) -> Vec<Token> {
+        let Some(tree) = &self.tree else {
+            return Vec::new();
+        };
+        if line_idx >= buffer.len_lines() {
+            return Vec::new();
+        }
+        let line_start = buffer.line_to_char(line_idx);
+        let start = buffer.char_to_byte(line_start);
+        let end = buffer.char_to_byte(line_start + buffer.line_len(line_idx));
+
+        let names = self.query.capture_names();
+        let mut cursor = QueryCursor::new();
+        cursor.set_byte_range(start..end);
+        let text = |node: Node| iter::once(node_text(buffer, node.byte_range()));
+        let mut captures = cursor.captures(self.query, tree.root_node(), text);
+
+        let mut tokens: Vec<Token> = Vec::new();
+        while let Some((found, index)) = captures.next() {
+            let capture = found.captures[*index];
+            let scope = scope_for(names[capture.index as usize]);
+            let bytes = capture.node.byte_range();
+            let range = buffer.byte_to_char(bytes.start.max(start)) - line_start
+                ..buffer.byte_to_char(bytes.end.min(end)) - line_start;
+            // The first pattern to capture a node wins.
+            if scope == "none" || range.is_empty() || tokens.iter().any(|t| t.range == range) {
+                continue;
+            }
+            tokens.push(Token { range, scope });
+        }
+        tokens
+    }
+
+    pub fn expand(&self, buffer: &RopeBuffer, range: Range<usize>) -> Option<Range<usize>> {
+        let node = self.node_at(buffer, &range)?;
+        let node = if char_range(buffer, node) == range {
+            outer_parent(node)?
+        } else {
+            node
+        };
+        Some(char_range(buffer, node))
+    }
+
+    pub fn parent(&self, buffer: &RopeBuffer, range: Range<usize>) -> Option<Range<usize>> {
+        let node = self.node_at(buffer, &range)?;
+        outer_parent(node).map(|node| char_range(buffer, node))
+    }
+
+    pub fn next_sibling(&self, buffer: &RopeBuffer, range: Range<usize>) -> Option<Range<usize>> {
+        let mut node = self.node_at(buffer, &range)?;
+        loop {
+            if let Some(sibling) = node.next_named_sibling() {
+                return Some(char_range(buffer, sibling));
+            }
+            node = node.parent()?;
+        }
+    }
+
+    pub fn previous_sibling(&self, buffer: &RopeBuffer, range: Range<usize>) -> Option<Range<usize>> {
+        let mut node = self.node_at(buffer, &range)?;
+        loop {
+            if let Some(sibling) = node.prev_named_sibling() {
+                return Some(char_range(buffer, sibling));
+            }
+            node = node.parent()?;
+        }
+    }
+
+    /// The smallest named node covering `range`, or the outermost one when
+    /// several share exactly that range.
+    fn node_at(&self, buffer: &RopeBuffer, range: &Range<usize>) -> Option<Node<'_>> {
+        let tree = self.tree.as_ref()?;
+        let start = buffer.char_to_byte(range.start);
+        let end = buffer.char_to_byte(range.end);
+        let mut node = tree.root_node().named_descendant_for_byte_range(start, end)?;
+        while let Some(parent) = node.parent()
+            && parent.byte_range() == node.byte_range()
+        {
+            node = parent;
+        }
+        Some(node)
+    }
+}
+
+fn outer_parent(node: Node<'_>) -> Option<Node<'_>> {
+    let mut parent = node.parent()?;
+    while parent.byte_range() == node.byte_range() || !parent.is_named() {
+        parent = parent.parent()?;
+    }
+    Some(parent)
+}
+
+fn scope_for(capture: &'static str) -> &'static str {
+    SCOPE_ALIASES
+        .iter()
+        .find(|(name, _)| *name == capture)
+        .map_or(capture, |(_, scope)| scope)
+}
+
+fn char_range(buffer: &RopeBuffer, node: Node<'_>) -> Range<usize> {
+    let bytes = node.byte_range();
+    buffer.byte_to_char(bytes.start)..buffer.byte_to_char(bytes.end)
+}
+
+fn node_text(buffer: &RopeBuffer, bytes: Range<usize>) -> String {
+    buffer.get_slice(buffer.byte_to_char(bytes.start), buffer.byte_to_char(bytes.end))
+}
+
+fn point((row, column): (usize, usize)) -> Point {
+    Point { row, column }
+}
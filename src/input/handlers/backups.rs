@@ -0,0 +1,66 @@
+use crate::features::file_operations::BackupFiles;
+use crate::input::InputAction;
+use crate::state::{EditorMode, EditorState};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::io;
+
+pub fn handle_backups_mode(
+    key: KeyEvent,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    match key.code {
+        KeyCode::Up => {
+            let selected = state.selected_backup().saturating_sub(1);
+            state.set_selected_backup(selected);
+        }
+        KeyCode::Down => {
+            let selected = state.selected_backup() + 1;
+            state.set_selected_backup(selected);
+        }
+        KeyCode::Enter => {
+            if let Some(entry) = state.backups().get(state.selected_backup()).cloned() {
+                match BackupFiles::open(&entry) {
+                    Ok(document) => {
+                        state.replace_document(document);
+                        state.message_mut().set(format!(
+                            "Viewing backup {} (read-only, unnamed copy)",
+                            entry.path.display()
+                        ));
+                    }
+                    Err(e) => {
+                        state
+                            .message_mut()
+                            .set(format!("Error opening backup: {}", e));
+                    }
+                }
+            }
+            state.set_mode(EditorMode::Normal);
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            if let Some(entry) = state.backups().get(state.selected_backup()).cloned() {
+                match BackupFiles::restore(state.document_mut(), &entry) {
+                    Ok(_) => {
+                        state.cursor_mut().reset();
+                        state.message_mut().set(format!(
+                            "Restored {} into buffer; save to keep it",
+                            entry.path.display()
+                        ));
+                    }
+                    Err(e) => {
+                        state
+                            .message_mut()
+                            .set(format!("Error restoring backup: {}", e));
+                    }
+                }
+            }
+            state.set_mode(EditorMode::Normal);
+        }
+        KeyCode::Esc => {
+            state.set_mode(EditorMode::Normal);
+            state.message_mut().clear();
+        }
+        _ => {}
+    }
+
+    Ok(Some(InputAction::Continue))
+}
@@ -0,0 +1,34 @@
+use crate::core::cursor::{CursorPosition, Selection};
+use crate::state::EditorState;
+
+pub(super) fn jump_to_match(state: &mut EditorState) {
+    let Some((_, partner)) = state.bracket_match() else {
+        state.message_mut().set("No matching bracket".to_string());
+        return;
+    };
+    let position = CursorPosition::from_char_idx(state.document().buffer(), partner);
+    state.set_selection(None);
+    let (cursor, viewport) = state.cursor_and_viewport_mut();
+    *cursor = position;
+    viewport.adjust_for_cursor(cursor.line);
+}
+
+/// Selects the text inside the innermost brackets, or the brackets too.
+/// Repeating it moves out to the next pair.
+pub(super) fn select_brackets(state: &mut EditorState, around: bool) {
+    let current = state.selection_range();
+    let mut pair = state.enclosing_brackets(current.clone());
+    if !around
+        && let Some((open, close)) = pair
+        && current == (open + 1..close)
+    {
+        pair = state.enclosing_brackets(open..close + 1);
+    }
+    let Some((open, close)) = pair else {
+        state.message_mut().set("No enclosing brackets".to_string());
+        return;
+    };
+    let range = if around { open..close + 1 } else { open + 1..close };
+    let selection = Selection::from_char_range(state.document().buffer(), range);
+    state.set_selection(Some(selection));
+}
@@ -1,31 +1,38 @@
-use crate::core::document::Document;
+use super::structure::{self, Motion};
+use super::{brackets, comment, explorer, file_actions, finder, history, recent, search, theme};
+use crate::config::ConfigLoader;
 use crate::features::command_palette::CommandFilter;
-use crate::features::file_operations::{SaveFile};
+use crate::features::file_operations::{AutoSaveSettings, BackupFiles};
 use crate::input::InputAction;
-use crate::state::{EditorMode, EditorState};
+use crate::state::{Confirmation, EditorMode, EditorState, PendingAction};
+use crate::ui::components::FieldEdit;
 use crossterm::event::{KeyCode, KeyEvent};
+use std::env;
 use std::io;
 
 pub fn handle_command_palette(
     key: KeyEvent,
     state: &mut EditorState,
 ) -> io::Result<Option<InputAction>> {
-    match key.code {
-        KeyCode::Char(c) => {
-            state.push_command_input(c);
-            update_filtered_commands(state);
-        }
-        KeyCode::Backspace => {
-            state.pop_command_input();
+    match state.command_field_mut().handle_key(&key) {
+        FieldEdit::Changed => {
             update_filtered_commands(state);
+            return Ok(Some(InputAction::Continue));
         }
+        FieldEdit::CursorMoved => return Ok(Some(InputAction::Continue)),
+        FieldEdit::Unhandled => {}
+    }
+
+    match key.code {
         KeyCode::Enter => {
+            state.set_mode(EditorMode::Normal);
             if !state.filtered_commands().is_empty() {
                 let command_id = state.filtered_commands()[0].id.clone();
+                state.clear_command_input();
                 execute_command(state, &command_id)?;
+            } else {
+                state.clear_command_input();
             }
-            state.set_mode(EditorMode::Normal);
-            state.clear_command_input();
         }
         KeyCode::Esc => {
             state.set_mode(EditorMode::Normal);
@@ -38,7 +45,7 @@ pub fn handle_command_palette(
     Ok(Some(InputAction::Continue))
 }
 
-fn update_filtered_commands(state: &mut EditorState) {
+pub(super) fn update_filtered_commands(state: &mut EditorState) {
     let all_commands = state.command_registry().all_commands();
     let filtered = CommandFilter::filter_commands(all_commands, state.command_input());
     state.set_filtered_commands(filtered);
@@ -47,43 +54,121 @@ fn update_filtered_commands(state: &mut EditorState) {
 fn execute_command(state: &mut EditorState, command_id: &str) -> io::Result<()> {
     match command_id {
         "open_file" => {
-            state.set_mode(EditorMode::OpenFile);
-            state.clear_command_input();
-            state
-                .message_mut()
-                .set("Enter file path to open:".to_string());
+            file_actions::prompt_open(state);
         }
+        "find_file" => finder::open_finder(state),
+        "open_recent" => recent::open_recent(state),
+        "next_file" => file_actions::switch_file(state, true),
+        "previous_file" => file_actions::switch_file(state, false),
+        "search" => search::prompt_search(state),
+        "goto_line" => search::prompt_goto(state),
+        "toggle_explorer" => explorer::toggle_explorer(state),
+        "focus_explorer" => explorer::focus_explorer(state),
+        "undo" => history::undo(state),
+        "redo" => history::redo(state),
         "save" => {
-            let has_file_path = state.document().file_path().is_some();
-            if !has_file_path {
-                state.set_mode(EditorMode::SaveAs);
-                state.clear_command_input();
-                state
-                    .message_mut()
-                    .set("Enter file path to save as:".to_string());
+            file_actions::save_current(state);
+        }
+        "save_as" => {
+            file_actions::prompt_save_as(state);
+        }
+        "new_file" => {
+            if state.document().is_modified() {
+                let file_name = state.document().file_name();
+                state.confirm(Confirmation::unsaved_changes(&file_name, PendingAction::NewFile));
             } else {
+                file_actions::new_file(state);
+            }
+        }
+        "close_file" => {
+            if state.document().is_modified() {
                 let file_name = state.document().file_name();
-                SaveFile::execute(state.document_mut())?;
+                state.confirm(Confirmation::unsaved_changes(&file_name, PendingAction::CloseFile));
+            } else {
+                file_actions::close_file(state);
+            }
+        }
+        "restore_session" => file_actions::restore_session(state),
+        "toggle_read_only" => {
+            // The first edit would copy the whole mapping into memory.
+            if state.document().buffer().is_mapped() {
+                let message = format!(
+                    "{} is memory-mapped and stays read-only; use Save As to keep a copy",
+                    state.document().file_name()
+                );
+                state.message_mut().set(message);
+                return Ok(());
+            }
+            let read_only = !state.document().is_read_only();
+            state.document_mut().set_read_only(read_only);
+            let message = if read_only {
+                "Read-only: editing and Save are disabled"
+            } else {
+                "Editing enabled"
+            };
+            state.message_mut().set(message.to_string());
+        }
+        "list_backups" => match state.document().file_path() {
+            Some(path) => {
+                let backups = BackupFiles::list(path);
+                if backups.is_empty() {
+                    state
+                        .message_mut()
+                        .set("No backups for this file".to_string());
+                } else {
+                    state.set_backups(backups);
+                    state.set_mode(EditorMode::Backups);
+                    state.message_mut().set(
+                        "Enter: open copy | R: restore into buffer | Esc: cancel".to_string(),
+                    );
+                }
+            }
+            None => {
                 state
                     .message_mut()
-                    .set(format!("Saved: {}", file_name));
+                    .set("Current buffer has no file path".to_string());
             }
-        }
-        "save_as" => {
-            state.set_mode(EditorMode::SaveAs);
-            state.clear_command_input();
+        },
+        "cycle_backup_policy" => {
+            let policy = state.backup_policy().next();
+            state.set_backup_policy(policy);
             state
                 .message_mut()
-                .set("Enter file path to save as:".to_string());
+                .set(format!("Backups on save: {}", policy.describe()));
         }
-        "new_file" => {
-            let new_document = Document::new();
-            state.replace_document(new_document);
-            state.message_mut().set("New file created".to_string());
+        "toggle_auto_save" => {
+            if state.auto_save().is_active() {
+                state.set_auto_save(AutoSaveSettings::off());
+                state.message_mut().set("Auto-save disabled".to_string());
+            } else {
+                state.set_auto_save(AutoSaveSettings::enabled());
+                state.message_mut().set(
+                    "Auto-save enabled (on idle and focus loss)".to_string(),
+                );
+            }
         }
-        "close_file" => {
-            let new_document = Document::new();
-            state.replace_document(new_document);
+        "toggle_line_comment" => comment::toggle_line_comment(state),
+        "toggle_block_comment" => comment::toggle_block_comment(state),
+        "jump_to_bracket" => brackets::jump_to_match(state),
+        "select_inside_brackets" => brackets::select_brackets(state, false),
+        "select_around_brackets" => brackets::select_brackets(state, true),
+        "expand_selection" => structure::apply(state, Motion::Expand),
+        "shrink_selection" => structure::apply(state, Motion::Shrink),
+        "goto_parent_node" => structure::apply(state, Motion::Parent),
+        "goto_next_sibling" => structure::apply(state, Motion::NextSibling),
+        "goto_previous_sibling" => structure::apply(state, Motion::PreviousSibling),
+        "select_theme" => theme::open_theme_picker(state),
+        "reload_config" => {
+            let loaded = ConfigLoader::load(&env::current_dir().unwrap_or_default());
+            state.apply_config(loaded.config.clone());
+            state.set_theme(loaded.theme.clone());
+            let message = loaded.error_message().unwrap_or_else(|| {
+                match loaded.sources.len() {
+                    0 => "No config files found; using defaults".to_string(),
+                    n => format!("Config reloaded ({} file{})", n, if n == 1 { "" } else { "s" }),
+                }
+            });
+            state.message_mut().set(message);
         }
         _ => {}
     }
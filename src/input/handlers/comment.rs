@@ -0,0 +1,113 @@
+use crate::core::cursor::{CursorPosition, Selection};
+use crate::features::editing::CommentFeature;
+use crate::state::EditorState;
+use std::ops::Range;
+
+/// Comments or uncomments the current or selected lines. Languages with
+/// only block comments get one around the lines.
+pub(super) fn toggle_line_comment(state: &mut EditorState) {
+    if state.refuse_read_only() {
+        return;
+    }
+    let grammar = state.grammar();
+    let Some(marker) = grammar.and_then(|g| g.line_comment) else {
+        match grammar.and_then(|g| g.block_comment) {
+            Some(delimiters) => {
+                let lines = selected_lines(state);
+                let buffer = state.document().buffer();
+                let start = buffer.line_to_char(lines.start);
+                let last = lines.end - 1;
+                let end = buffer.line_to_char(last) + buffer.line_len(last);
+                toggle_block(state, start..end, delimiters);
+            }
+            None => no_comment_syntax(state),
+        }
+        return;
+    };
+
+    let lines = selected_lines(state);
+    let selection = state.selection().copied();
+    let Some((buffer, cursor)) = state.buffer_and_cursor_mut() else {
+        return;
+    };
+    let shifts = CommentFeature::toggle_line(buffer, lines, marker);
+    for shift in &shifts {
+        shift.apply(cursor);
+    }
+    if let Some(mut selection) = selection {
+        for shift in &shifts {
+            shift.apply(&mut selection.anchor);
+            shift.apply(&mut selection.head);
+        }
+        state.set_selection(Some(selection));
+    }
+    let (cursor, viewport) = state.cursor_and_viewport_mut();
+    viewport.adjust_for_cursor(cursor.line);
+}
+
+pub(super) fn toggle_block_comment(state: &mut EditorState) {
+    if state.refuse_read_only() {
+        return;
+    }
+    let Some(delimiters) = state.grammar().and_then(|g| g.block_comment) else {
+        no_comment_syntax(state);
+        return;
+    };
+    let range = match state.selection() {
+        Some(_) => state.selection_range(),
+        None => {
+            let buffer = state.document().buffer();
+            let line = state.cursor().line;
+            let start = buffer.line_to_char(line);
+            start..start + buffer.line_len(line)
+        }
+    };
+    toggle_block(state, range, delimiters);
+}
+
+fn toggle_block(state: &mut EditorState, range: Range<usize>, delimiters: (&str, &str)) {
+    let had_selection = state.selection().is_some();
+    let cursor_idx = state.selection_range().start;
+    let Some((buffer, cursor)) = state.buffer_and_cursor_mut() else {
+        return;
+    };
+    let (result, moved) = CommentFeature::toggle_block(buffer, range.clone(), delimiters);
+    if had_selection {
+        let selection = Selection::from_char_range(buffer, result);
+        state.set_selection(Some(selection));
+    } else {
+        let idx = if cursor_idx > range.start {
+            cursor_idx.saturating_add_signed(moved).clamp(result.start, result.end)
+        } else {
+            cursor_idx
+        };
+        *cursor = CursorPosition::from_char_idx(buffer, idx);
+    }
+    let (cursor, viewport) = state.cursor_and_viewport_mut();
+    viewport.adjust_for_cursor(cursor.line);
+}
+
+/// The lines the selection touches, or the cursor line. A selection ending
+/// at the start of a line leaves that line out.
+fn selected_lines(state: &EditorState) -> Range<usize> {
+    match state.selection() {
+        Some(selection) => {
+            let (start, end) = (selection.start(), selection.end());
+            let last = if end.column == 0 && end.line > start.line {
+                end.line - 1
+            } else {
+                end.line
+            };
+            start.line..last + 1
+        }
+        None => state.cursor().line..state.cursor().line + 1,
+    }
+}
+
+fn no_comment_syntax(state: &mut EditorState) {
+    let message = match state.grammar() {
+        Some(grammar) => format!("{} has no comment syntax", grammar.name),
+        None => "Unknown language; cannot toggle comments".to_string(),
+    };
+    state.message_mut().set(message);
+}
@@ -0,0 +1,131 @@
+use super::{explorer, file_actions};
+use crate::input::InputAction;
+use crate::state::{ConfirmChoice, EditorMode, EditorState, PendingAction};
+use crate::ui::components::dialogs::confirmation_button_at;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use std::io;
+
+pub fn handle_confirmation(
+    key: KeyEvent,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    let Some(confirmation) = state.confirmation_mut() else {
+        return Ok(Some(InputAction::Continue));
+    };
+    let dialog = &mut confirmation.dialog;
+    let is_quit = confirmation.action == PendingAction::Quit;
+
+    let choice = match key.code {
+        KeyCode::Left | KeyCode::BackTab => {
+            dialog.select_previous();
+            None
+        }
+        KeyCode::Right | KeyCode::Tab => {
+            dialog.select_next();
+            None
+        }
+        KeyCode::Enter => Some(dialog.selected()),
+        KeyCode::Esc => Some(dialog.cancel_choice()),
+        KeyCode::Char('q') if is_quit && key.modifiers.contains(KeyModifiers::CONTROL) => {
+            Some(ConfirmChoice::Discard)
+        }
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            dialog.choice_for_key('s')
+        }
+        KeyCode::Char(c) => dialog.choice_for_key(c),
+        _ => None,
+    };
+
+    match choice {
+        Some(choice) => resolve(state, choice),
+        None => Ok(Some(InputAction::Continue)),
+    }
+}
+
+pub fn handle_confirmation_mouse(
+    mouse: MouseEvent,
+    screen: Rect,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    let Some(confirmation) = state.confirmation_mut() else {
+        return Ok(Some(InputAction::Continue));
+    };
+    let Some(index) = confirmation_button_at(&confirmation.dialog, screen, mouse.column, mouse.row)
+    else {
+        return Ok(Some(InputAction::Continue));
+    };
+
+    match mouse.kind {
+        MouseEventKind::Moved => {
+            confirmation.dialog.select(index);
+            Ok(Some(InputAction::Continue))
+        }
+        MouseEventKind::Down(MouseButton::Left) => {
+            confirmation.dialog.select(index);
+            let choice = confirmation.dialog.selected();
+            resolve(state, choice)
+        }
+        _ => Ok(Some(InputAction::Continue)),
+    }
+}
+
+fn resolve(state: &mut EditorState, choice: ConfirmChoice) -> io::Result<Option<InputAction>> {
+    let Some(confirmation) = state.take_confirmation() else {
+        return Ok(Some(InputAction::Continue));
+    };
+
+    if confirmation.action.is_explorer() {
+        state.set_mode(EditorMode::Explorer);
+    }
+
+    match choice {
+        ConfirmChoice::Cancel | ConfirmChoice::Keep => {
+            state.set_after_save_as(None);
+            state.message_mut().set("Canceled".to_string());
+            Ok(Some(InputAction::Continue))
+        }
+        ConfirmChoice::Save if state.document().file_path().is_none() => {
+            state.set_after_save_as(Some(confirmation.action));
+            file_actions::prompt_save_as(state);
+            Ok(Some(InputAction::Continue))
+        }
+        ConfirmChoice::Save => {
+            if file_actions::save_current(state) {
+                Ok(perform(state, confirmation.action))
+            } else {
+                Ok(Some(InputAction::Continue))
+            }
+        }
+        ConfirmChoice::Discard
+        | ConfirmChoice::Overwrite
+        | ConfirmChoice::Reload
+        | ConfirmChoice::Delete => {
+            Ok(perform(state, confirmation.action))
+        }
+    }
+}
+
+pub(super) fn save_as(state: &mut EditorState, path: &str) -> Option<InputAction> {
+    let resume = state.take_after_save_as();
+    let saved = file_actions::save_as(state, path);
+    match resume {
+        Some(action) if saved => perform(state, action),
+        _ => Some(InputAction::Continue),
+    }
+}
+
+fn perform(state: &mut EditorState, action: PendingAction) -> Option<InputAction> {
+    match action {
+        PendingAction::Quit => return Some(InputAction::Quit),
+        PendingAction::NewFile => file_actions::new_file(state),
+        PendingAction::CloseFile => file_actions::close_file(state),
+        PendingAction::OpenFile(path) => file_actions::open(state, &path),
+        PendingAction::SaveAs(path) => return save_as(state, &path),
+        PendingAction::Reload => file_actions::reload(state),
+        PendingAction::DeletePath(path) => explorer::delete_path(state, &path),
+        PendingAction::MovePath(from, to) => explorer::move_path(state, &from, &to),
+        PendingAction::RestoreSession => file_actions::load_session(state),
+    }
+    Some(InputAction::Continue)
+}
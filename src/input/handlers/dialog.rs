@@ -1,6 +1,9 @@
-use crate::features::file_operations::{OpenFile, SaveFileAs};
+use super::{confirmation, file_actions};
+use crate::features::file_operations::PathCompletion;
 use crate::input::InputAction;
-use crate::state::{EditorMode, EditorState};
+use crate::state::{Confirmation, EditorMode, EditorState, PendingAction};
+use crate::ui::components::FieldEdit;
+use crate::utils::paths;
 use crossterm::event::{KeyCode, KeyEvent};
 use std::io;
 
@@ -8,19 +11,39 @@ pub fn handle_dialog_mode(
     key: KeyEvent,
     state: &mut EditorState,
 ) -> io::Result<Option<InputAction>> {
-    match key.code {
-        KeyCode::Char(c) => {
-            state.push_command_input(c);
+    match state.command_field_mut().handle_key(&key) {
+        FieldEdit::Changed => {
+            file_actions::refresh_path_candidates(state);
+            return Ok(Some(InputAction::Continue));
         }
-        KeyCode::Backspace => {
-            state.pop_command_input();
+        FieldEdit::CursorMoved => return Ok(Some(InputAction::Continue)),
+        FieldEdit::Unhandled => {}
+    }
+
+    match key.code {
+        KeyCode::Tab => complete_path(state),
+        KeyCode::Up => {
+            let selected = match state.selected_candidate() {
+                Some(0) | None => None,
+                Some(i) => Some(i - 1),
+            };
+            state.set_selected_candidate(selected);
         }
-        KeyCode::Enter => {
-            execute_dialog_action(state)?;
+        KeyCode::Down => {
+            let count = state.path_candidates().len();
+            let selected = match state.selected_candidate() {
+                None if count > 0 => Some(0),
+                Some(i) if i + 1 < count => Some(i + 1),
+                other => other,
+            };
+            state.set_selected_candidate(selected);
         }
+        KeyCode::Enter => return Ok(execute_dialog_action(state)),
         KeyCode::Esc => {
             state.set_mode(EditorMode::Normal);
             state.clear_command_input();
+            state.set_path_candidates(Vec::new());
+            state.set_after_save_as(None);
             state.message_mut().clear();
         }
         _ => {}
@@ -29,43 +52,81 @@ pub fn handle_dialog_mode(
     Ok(Some(InputAction::Continue))
 }
 
-fn execute_dialog_action(state: &mut EditorState) -> io::Result<()> {
+pub(super) fn complete_path(state: &mut EditorState) {
+    if let Some(index) = state.selected_candidate() {
+        let value = state.path_candidates()[index].value.clone();
+        state.set_command_input(value);
+        file_actions::refresh_path_candidates(state);
+        return;
+    }
+
     let input = state.command_input().to_string();
+    match PathCompletion::complete(&input, state.path_candidates()) {
+        Some(completed) => {
+            state.set_command_input(completed);
+            file_actions::refresh_path_candidates(state);
+        }
+        None if state.path_candidates().is_empty() => {
+            state.message_mut().set("No matches".to_string());
+        }
+        None => {
+            let count = state.path_candidates().len();
+            state
+                .message_mut()
+                .set(format!("{} candidates; keep typing or use Up/Down", count));
+        }
+    }
+}
 
-    match state.mode() {
-        EditorMode::OpenFile => {
-            if !input.is_empty() {
-                match OpenFile::execute(&input) {
-                    Ok(document) => {
-                        state.replace_document(document);
-                        state.message_mut().set(format!("Opened: {}", input));
-                    }
-                    Err(e) => {
-                        state
-                            .message_mut()
-                            .set(format!("Error opening file: {}", e));
-                    }
-                }
-            }
+fn execute_dialog_action(state: &mut EditorState) -> Option<InputAction> {
+    if let Some(index) = state.selected_candidate() {
+        let candidate = state.path_candidates()[index].clone();
+        state.set_command_input(candidate.value);
+        if candidate.is_dir {
+            file_actions::refresh_path_candidates(state);
+            return Some(InputAction::Continue);
         }
-        EditorMode::SaveAs => {
-            if !input.is_empty() {
-                match SaveFileAs::execute(state.document_mut(), &input) {
-                    Ok(_) => {
-                        state.message_mut().set(format!("Saved as: {}", input));
-                    }
-                    Err(e) => {
-                        state
-                            .message_mut()
-                            .set(format!("Error saving file: {}", e));
-                    }
-                }
-            }
+    }
+
+    let input = state.command_input().to_string();
+    if !input.is_empty() && paths::expand_tilde(&input).is_dir() {
+        let mut dir = input;
+        if !dir.ends_with(std::path::MAIN_SEPARATOR) {
+            dir.push(std::path::MAIN_SEPARATOR);
         }
-        _ => {}
+        state.set_command_input(dir);
+        file_actions::refresh_path_candidates(state);
+        return Some(InputAction::Continue);
     }
 
+    let mode = state.mode().clone();
     state.set_mode(EditorMode::Normal);
     state.clear_command_input();
-    Ok(())
-}
\ No newline at end of file
+    state.set_path_candidates(Vec::new());
+
+    match mode {
+        EditorMode::OpenFile if !input.is_empty() => {
+            if state.document().is_modified() {
+                let file_name = state.document().file_name();
+                state.confirm(Confirmation::unsaved_changes(
+                    &file_name,
+                    PendingAction::OpenFile(input),
+                ));
+            } else {
+                file_actions::open(state, &input);
+            }
+        }
+        EditorMode::SaveAs if !input.is_empty() => {
+            let target = paths::expand_tilde(&input);
+            let is_current = state.document().file_path().is_some_and(|p| *p == target);
+            if target.exists() && !is_current {
+                state.confirm(Confirmation::overwrite(&input));
+            } else {
+                return confirmation::save_as(state, &input);
+            }
+        }
+        _ => state.set_after_save_as(None),
+    }
+
+    Some(InputAction::Continue)
+}
@@ -0,0 +1,279 @@
+use super::{dialog, file_actions, normal};
+use crate::core::document::SwapFile;
+use crate::features::explorer::{ExplorerOperations, ExplorerPrompt};
+use crate::input::InputAction;
+use crate::state::{Confirmation, EditorMode, EditorState, PendingAction};
+use crate::ui::components::FieldEdit;
+use crate::utils::paths;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::env;
+use std::io;
+use std::path::Path;
+
+const EXPLORER_HINT: &str =
+    "a: new file | A: new dir | r: rename | m: move | d: delete | Esc: editor";
+
+pub(super) fn toggle_explorer(state: &mut EditorState) {
+    if state.is_explorer_visible() {
+        state.set_explorer_visible(false);
+        state.message_mut().clear();
+    } else {
+        focus_explorer(state);
+    }
+}
+
+pub(super) fn focus_explorer(state: &mut EditorState) {
+    if !state.is_explorer_visible() {
+        let root = match env::current_dir() {
+            Ok(root) => root,
+            Err(e) => {
+                state
+                    .message_mut()
+                    .set(format!("Cannot read working directory: {}", e));
+                return;
+            }
+        };
+        state.explorer_mut().set_root(root);
+        state.set_explorer_visible(true);
+    }
+    if let Some(path) = state.document().file_path().map(|p| paths::absolute(p)) {
+        state.explorer_mut().reveal(&path);
+    }
+    state.set_mode(EditorMode::Explorer);
+    state.message_mut().set(EXPLORER_HINT.to_string());
+}
+
+pub fn handle_explorer_mode(
+    key: KeyEvent,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return match key.code {
+            KeyCode::Char('b') => {
+                toggle_explorer(state);
+                Ok(Some(InputAction::Continue))
+            }
+            KeyCode::Char('q' | 's' | 'p' | 'o' | 't') => normal::handle_normal_mode(key, state),
+            _ => Ok(Some(InputAction::Continue)),
+        };
+    }
+
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => state.explorer_mut().select_previous(),
+        KeyCode::Down | KeyCode::Char('j') => state.explorer_mut().select_next(),
+        KeyCode::Left | KeyCode::Char('h') => state.explorer_mut().collapse(),
+        KeyCode::Right | KeyCode::Char('l') => state.explorer_mut().expand(),
+        KeyCode::Enter => activate(state),
+        KeyCode::Char('a') => {
+            let dir = state.explorer().selected_dir();
+            prompt(state, ExplorerPrompt::NewFile(dir));
+        }
+        KeyCode::Char('A') => {
+            let dir = state.explorer().selected_dir();
+            prompt(state, ExplorerPrompt::NewDirectory(dir));
+        }
+        KeyCode::Char('r') | KeyCode::F(2) => {
+            if let Some(entry) = state.explorer().selected_entry() {
+                let path = entry.path.clone();
+                prompt(state, ExplorerPrompt::Rename(path));
+            }
+        }
+        KeyCode::Char('m') => {
+            if let Some(entry) = state.explorer().selected_entry() {
+                let path = entry.path.clone();
+                prompt(state, ExplorerPrompt::Move(path));
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            if let Some(entry) = state.explorer().selected_entry() {
+                let path = entry.path.clone();
+                let display = display_path(state, &path);
+                state.confirm(Confirmation::delete(path, &display));
+            }
+        }
+        KeyCode::Char('R') => {
+            state.explorer_mut().refresh();
+            state.message_mut().set("Explorer refreshed".to_string());
+        }
+        KeyCode::Char('.') => state.explorer_mut().toggle_hidden(),
+        KeyCode::Esc | KeyCode::Tab => {
+            state.set_mode(EditorMode::Normal);
+            state.message_mut().clear();
+        }
+        _ => {}
+    }
+
+    Ok(Some(InputAction::Continue))
+}
+
+pub fn handle_explorer_input(
+    key: KeyEvent,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    let completes_paths = state
+        .explorer_prompt()
+        .is_some_and(|p| p.completes_paths());
+
+    match state.command_field_mut().handle_key(&key) {
+        FieldEdit::Changed => {
+            if completes_paths {
+                file_actions::refresh_path_candidates(state);
+            }
+            return Ok(Some(InputAction::Continue));
+        }
+        FieldEdit::CursorMoved => return Ok(Some(InputAction::Continue)),
+        FieldEdit::Unhandled => {}
+    }
+
+    match key.code {
+        KeyCode::Tab if completes_paths => dialog::complete_path(state),
+        KeyCode::Enter => {
+            let input = state.command_input().to_string();
+            let prompt = state.explorer_prompt().cloned();
+            close_prompt(state);
+            if let Some(prompt) = prompt
+                && !input.is_empty()
+            {
+                apply(state, prompt, &input);
+            }
+        }
+        KeyCode::Esc => {
+            close_prompt(state);
+            state.message_mut().clear();
+        }
+        _ => {}
+    }
+
+    Ok(Some(InputAction::Continue))
+}
+
+pub(super) fn delete_path(state: &mut EditorState, path: &Path) {
+    let display = display_path(state, path);
+    match ExplorerOperations::delete(path) {
+        Ok(()) => {
+            let open_deleted = state
+                .document()
+                .file_path()
+                .is_some_and(|p| paths::absolute(p).starts_with(path));
+            if open_deleted {
+                state.document_mut().mark_modified();
+                state
+                    .message_mut()
+                    .set(format!("Deleted {}; the open buffer is kept", display));
+            } else {
+                state.message_mut().set(format!("Deleted: {}", display));
+            }
+        }
+        Err(e) => state.message_mut().set(format!("Delete failed: {}", e)),
+    }
+    state.explorer_mut().refresh();
+}
+
+pub(super) fn move_path(state: &mut EditorState, from: &Path, to: &Path) {
+    if let Err(e) = ExplorerOperations::move_path(from, to) {
+        state.message_mut().set(format!("Move failed: {}", e));
+        return;
+    }
+
+    let moved_document = state
+        .document()
+        .file_path()
+        .map(|p| paths::absolute(p))
+        .and_then(|p| p.strip_prefix(from).ok().map(|rest| to.join(rest)));
+    if let Some(new_path) = moved_document {
+        let document = state.document_mut();
+        let _ = SwapFile::remove(document);
+        document.set_file_path(new_path.clone());
+        let _ = SwapFile::remove_for(&new_path);
+    }
+
+    let display = display_path(state, to);
+    state.message_mut().set(format!("Moved to: {}", display));
+    state.explorer_mut().reveal(to);
+}
+
+fn activate(state: &mut EditorState) {
+    let Some(entry) = state.explorer().selected_entry() else {
+        return;
+    };
+    if entry.is_dir {
+        state.explorer_mut().toggle();
+        return;
+    }
+
+    let path = entry.path.to_string_lossy().into_owned();
+    state.set_mode(EditorMode::Normal);
+    if state.document().is_modified() {
+        let file_name = state.document().file_name();
+        state.confirm(Confirmation::unsaved_changes(
+            &file_name,
+            PendingAction::OpenFile(path),
+        ));
+    } else {
+        file_actions::open(state, &path);
+    }
+}
+
+fn prompt(state: &mut EditorState, prompt: ExplorerPrompt) {
+    let input = prompt.initial_input(state.explorer().root());
+    let completes_paths = prompt.completes_paths();
+    state.set_explorer_prompt(Some(prompt));
+    state.set_mode(EditorMode::ExplorerInput);
+    state.set_command_input(input);
+    if completes_paths {
+        file_actions::refresh_path_candidates(state);
+    } else {
+        state.set_path_candidates(Vec::new());
+    }
+}
+
+fn close_prompt(state: &mut EditorState) {
+    state.set_explorer_prompt(None);
+    state.set_mode(EditorMode::Explorer);
+    state.clear_command_input();
+    state.set_path_candidates(Vec::new());
+}
+
+fn apply(state: &mut EditorState, prompt: ExplorerPrompt, input: &str) {
+    let target = prompt.target(input);
+    let display = display_path(state, &target);
+
+    match prompt {
+        ExplorerPrompt::NewFile(_) | ExplorerPrompt::NewDirectory(_) if target.exists() => {
+            state
+                .message_mut()
+                .set(format!("{} already exists", display));
+        }
+        ExplorerPrompt::NewFile(_) => match ExplorerOperations::create_file(&target) {
+            Ok(()) => {
+                state.message_mut().set(format!("Created: {}", display));
+                state.explorer_mut().reveal(&target);
+            }
+            Err(e) => state.message_mut().set(format!("Create failed: {}", e)),
+        },
+        ExplorerPrompt::NewDirectory(_) => match ExplorerOperations::create_dir(&target) {
+            Ok(()) => {
+                state.message_mut().set(format!("Created: {}", display));
+                state.explorer_mut().reveal(&target);
+            }
+            Err(e) => state.message_mut().set(format!("Create failed: {}", e)),
+        },
+        ExplorerPrompt::Rename(from) | ExplorerPrompt::Move(from) => {
+            if target == from {
+                return;
+            }
+            if target.exists() {
+                state.confirm(Confirmation::move_over(from, target, &display));
+            } else {
+                move_path(state, &from, &target);
+            }
+        }
+    }
+}
+
+fn display_path(state: &EditorState, path: &Path) -> String {
+    path.strip_prefix(state.explorer().root())
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
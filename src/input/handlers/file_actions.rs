@@ -0,0 +1,203 @@
+use crate::core::cursor::CursorMovement;
+use crate::core::document::Document;
+use crate::features::file_operations::{
+    ArgList, OpenFile, PathCompletion, ReloadFile, SaveFile, SaveFileAs,
+};
+use crate::features::session::{FilePositions, Session};
+use crate::state::{Confirmation, EditorMode, EditorState, PendingAction};
+use crate::utils::paths;
+use std::env;
+use std::path::{Path, PathBuf};
+
+pub(super) fn prompt_open(state: &mut EditorState) {
+    state.set_mode(EditorMode::OpenFile);
+    state.clear_command_input();
+    refresh_path_candidates(state);
+    state
+        .message_mut()
+        .set("Enter file path to open (Tab completes):".to_string());
+}
+
+pub(super) fn prompt_save_as(state: &mut EditorState) {
+    state.set_mode(EditorMode::SaveAs);
+    state.clear_command_input();
+    refresh_path_candidates(state);
+    state
+        .message_mut()
+        .set("Enter file path to save as:".to_string());
+}
+
+pub(super) fn refresh_path_candidates(state: &mut EditorState) {
+    let candidates = PathCompletion::candidates(state.command_input());
+    state.set_path_candidates(candidates);
+}
+
+pub(super) fn save_current(state: &mut EditorState) -> bool {
+    if state.document().file_path().is_none() {
+        prompt_save_as(state);
+        return false;
+    }
+
+    let file_name = state.document().file_name();
+    if state.document().is_read_only() {
+        state
+            .message_mut()
+            .set(format!("{} is read-only; use Save As", file_name));
+        return false;
+    }
+    let backup = state.backup_policy();
+    match SaveFile::execute(state.document_mut(), backup) {
+        Ok(_) => {
+            // Saving may have trimmed the cursor line.
+            let (buffer, cursor) = state.buffer_cursor();
+            CursorMovement::clamp(cursor, buffer);
+            state.message_mut().set(format!("Saved: {}", file_name));
+            true
+        }
+        Err(e) => {
+            state.message_mut().set(format!("Save failed: {}", e));
+            false
+        }
+    }
+}
+
+pub(super) fn save_as(state: &mut EditorState, path: &str) -> bool {
+    let backup = state.backup_policy();
+    let target = paths::expand_tilde(path);
+    match SaveFileAs::execute(state.document_mut(), &target.to_string_lossy(), backup) {
+        Ok(_) => {
+            let (buffer, cursor) = state.buffer_cursor();
+            CursorMovement::clamp(cursor, buffer);
+            state.detect_syntax();
+            state.message_mut().set(format!("Saved as: {}", path));
+            true
+        }
+        Err(e) => {
+            state
+                .message_mut()
+                .set(format!("Error saving file: {}", e));
+            false
+        }
+    }
+}
+
+pub(super) fn open(state: &mut EditorState, path: &str) {
+    let tasks = state.tasks().cloned();
+    match OpenFile::open_with(path, state.large_files(), tasks.as_ref()) {
+        Ok(mut opened) => {
+            if state.arg_list().is_read_only() && state.arg_list().contains(Path::new(path)) {
+                opened.document.set_read_only(true);
+                if let Some(progress) = &mut opened.loading {
+                    progress.read_only = true;
+                }
+            }
+            let message = if opened.created {
+                format!("New file: {}", path)
+            } else if opened.mapped {
+                format!("Opened memory-mapped, read-only: {}", path)
+            } else if opened.loading.is_some() {
+                format!("Loading: {}", path)
+            } else {
+                format!("Opened: {}", path)
+            };
+            state.message_mut().set(message);
+            match opened.loading {
+                Some(progress) => state.start_loading(opened.document, progress),
+                None => state.replace_document(opened.document),
+            }
+            if !ArgList::apply_position(state) {
+                FilePositions::restore(state);
+            }
+        }
+        Err(e) => {
+            state
+                .message_mut()
+                .set(format!("Error opening file: {}", e));
+        }
+    }
+}
+
+pub(super) fn new_file(state: &mut EditorState) {
+    state.replace_document(Document::new());
+    state.message_mut().set("New file created".to_string());
+}
+
+pub(super) fn close_file(state: &mut EditorState) {
+    state.replace_document(Document::new());
+    state.message_mut().clear();
+}
+
+pub(super) fn reload(state: &mut EditorState) {
+    match ReloadFile::execute(state.document_mut()) {
+        Ok(_) => {
+            let (buffer, cursor) = state.buffer_cursor();
+            CursorMovement::clamp(cursor, buffer);
+            let message = format!("Reloaded: {}", state.document().file_name());
+            state.message_mut().set(message);
+        }
+        Err(e) => state.message_mut().set(format!("Reload failed: {}", e)),
+    }
+}
+
+pub(super) fn switch_file(state: &mut EditorState, forward: bool) {
+    let current = state.document().file_path().map(PathBuf::as_path);
+    let target = if forward {
+        state.arg_list().next_after(current)
+    } else {
+        state.arg_list().previous_before(current)
+    };
+    let Some(path) = target.map(|p| p.to_string_lossy().into_owned()) else {
+        let edge = if forward { "last" } else { "first" };
+        state
+            .message_mut()
+            .set(format!("Already at the {} file", edge));
+        return;
+    };
+
+    if state.document().is_modified() {
+        let file_name = state.document().file_name();
+        state.confirm(Confirmation::unsaved_changes(
+            &file_name,
+            PendingAction::OpenFile(path),
+        ));
+    } else {
+        open(state, &path);
+    }
+}
+
+pub(super) fn restore_session(state: &mut EditorState) {
+    if state.document().is_modified() {
+        let file_name = state.document().file_name();
+        state.confirm(Confirmation::unsaved_changes(
+            &file_name,
+            PendingAction::RestoreSession,
+        ));
+    } else {
+        load_session(state);
+    }
+}
+
+pub(super) fn load_session(state: &mut EditorState) {
+    let dir = match env::current_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            state
+                .message_mut()
+                .set(format!("Cannot read working directory: {}", e));
+            return;
+        }
+    };
+    match Session::load(&dir).and_then(|session| {
+        session.map(|s| s.restore(state).map(|_| s.files.len())).transpose()
+    }) {
+        Ok(Some(count)) => state
+            .message_mut()
+            .set(format!("Session restored ({} files)", count)),
+        Ok(None) => state
+            .message_mut()
+            .set("No saved session for this directory".to_string()),
+        Err(e) => state
+            .message_mut()
+            .set(format!("Session restore failed: {}", e)),
+    }
+}
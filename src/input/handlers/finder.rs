@@ -0,0 +1,81 @@
+use super::file_actions;
+use crate::features::finder::FileIndexer;
+use crate::input::InputAction;
+use crate::state::{Confirmation, EditorMode, EditorState, PendingAction};
+use crate::ui::components::FieldEdit;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::env;
+use std::io;
+
+pub(super) fn open_finder(state: &mut EditorState) {
+    let root = match env::current_dir() {
+        Ok(root) => root,
+        Err(e) => {
+            state
+                .message_mut()
+                .set(format!("Cannot read working directory: {}", e));
+            return;
+        }
+    };
+
+    state.set_mode(EditorMode::FindFile);
+    state.clear_command_input();
+    let generation = state.file_picker_mut().start(root.clone());
+    match state.tasks() {
+        Some(tasks) => FileIndexer::spawn(root, generation, tasks),
+        None => state
+            .message_mut()
+            .set("File indexing is unavailable".to_string()),
+    }
+}
+
+pub fn handle_finder_mode(
+    key: KeyEvent,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    match state.command_field_mut().handle_key(&key) {
+        FieldEdit::Changed => {
+            let query = state.command_input().to_string();
+            state.file_picker_mut().set_query(&query);
+            return Ok(Some(InputAction::Continue));
+        }
+        FieldEdit::CursorMoved => return Ok(Some(InputAction::Continue)),
+        FieldEdit::Unhandled => {}
+    }
+
+    match key.code {
+        KeyCode::Up => state.file_picker_mut().select_previous(),
+        KeyCode::Down => state.file_picker_mut().select_next(),
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.file_picker_mut().select_previous()
+        }
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.file_picker_mut().select_next()
+        }
+        KeyCode::Enter => {
+            let Some(path) = state.file_picker().selected_path() else {
+                return Ok(Some(InputAction::Continue));
+            };
+            let path = path.to_string_lossy().into_owned();
+            state.set_mode(EditorMode::Normal);
+            state.clear_command_input();
+            if state.document().is_modified() {
+                let file_name = state.document().file_name();
+                state.confirm(Confirmation::unsaved_changes(
+                    &file_name,
+                    PendingAction::OpenFile(path),
+                ));
+            } else {
+                file_actions::open(state, &path);
+            }
+        }
+        KeyCode::Esc => {
+            state.set_mode(EditorMode::Normal);
+            state.clear_command_input();
+            state.message_mut().clear();
+        }
+        _ => {}
+    }
+
+    Ok(Some(InputAction::Continue))
+}
@@ -0,0 +1,33 @@
+use crate::core::cursor::CursorPosition;
+use crate::state::EditorState;
+
+pub(super) fn undo(state: &mut EditorState) {
+    if state.refuse_read_only() {
+        return;
+    }
+    let result = state.document_mut().undo();
+    apply(state, result, "Nothing to undo");
+}
+
+pub(super) fn redo(state: &mut EditorState) {
+    if state.refuse_read_only() {
+        return;
+    }
+    let result = state.document_mut().redo();
+    apply(state, result, "Nothing to redo");
+}
+
+fn apply(state: &mut EditorState, result: std::io::Result<Option<usize>>, empty: &str) {
+    match result {
+        Ok(Some(char_idx)) => {
+            let position = CursorPosition::from_char_idx(state.document().buffer(), char_idx);
+            state.set_selection(None);
+            let (cursor, viewport) = state.cursor_and_viewport_mut();
+            *cursor = position;
+            viewport.adjust_for_cursor(cursor.line);
+            state.message_mut().clear();
+        }
+        Ok(None) => state.message_mut().set(empty.to_string()),
+        Err(e) => state.message_mut().set(format!("Error: {}", e)),
+    }
+}
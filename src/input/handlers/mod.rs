@@ -1,10 +1,27 @@
+mod backups;
+mod brackets;
 mod command_palette;
+mod comment;
+mod confirmation;
 mod dialog;
+mod explorer;
+mod file_actions;
+mod finder;
+mod history;
 mod normal;
+mod pager;
+mod recent;
+mod recovery;
+mod search;
+mod structure;
+mod theme;
 
+use crate::features::file_operations::AutoSave;
 use crate::input::InputAction;
 use crate::state::{EditorMode, EditorState};
 use crossterm::event::{Event, KeyEventKind};
+use crossterm::terminal;
+use ratatui::layout::Rect;
 use std::io;
 
 pub struct InputHandler;
@@ -25,8 +42,56 @@ impl InputHandler {
                     EditorMode::OpenFile | EditorMode::SaveAs => {
                         dialog::handle_dialog_mode(key, state)
                     }
+                    EditorMode::Recovery => recovery::handle_recovery_mode(key, state),
+                    EditorMode::Backups => backups::handle_backups_mode(key, state),
+                    EditorMode::Confirm => confirmation::handle_confirmation(key, state),
+                    EditorMode::FindFile => finder::handle_finder_mode(key, state),
+                    EditorMode::RecentFiles => recent::handle_recent_mode(key, state),
+                    EditorMode::SelectTheme => theme::handle_theme_mode(key, state),
+                    EditorMode::Explorer => explorer::handle_explorer_mode(key, state),
+                    EditorMode::ExplorerInput => explorer::handle_explorer_input(key, state),
+                    EditorMode::Search | EditorMode::GotoLine => {
+                        search::handle_prompt_mode(key, state)
+                    }
                 }
             }
+            Event::Mouse(mouse) if *state.mode() == EditorMode::Confirm => {
+                let (width, height) = terminal::size()?;
+                let screen = Rect::new(0, 0, width, height);
+                confirmation::handle_confirmation_mouse(mouse, screen, state)
+            }
+            Event::Paste(text) => {
+                match state.mode() {
+                    EditorMode::Normal => normal::handle_paste(&text, state),
+                    EditorMode::CommandPalette => {
+                        state.command_field_mut().insert_str(&text);
+                        command_palette::update_filtered_commands(state);
+                    }
+                    EditorMode::OpenFile | EditorMode::SaveAs => {
+                        state.command_field_mut().insert_str(&text);
+                        file_actions::refresh_path_candidates(state);
+                    }
+                    EditorMode::ExplorerInput | EditorMode::Search | EditorMode::GotoLine => {
+                        state.command_field_mut().insert_str(&text)
+                    }
+                    EditorMode::FindFile => {
+                        state.command_field_mut().insert_str(&text);
+                        let query = state.command_input().to_string();
+                        state.file_picker_mut().set_query(&query);
+                    }
+                    EditorMode::RecentFiles => {
+                        state.command_field_mut().insert_str(&text);
+                        let query = state.command_input().to_string();
+                        state.recent_files_mut().set_query(&query);
+                    }
+                    _ => {}
+                }
+                Ok(Some(InputAction::Continue))
+            }
+            Event::FocusLost => {
+                AutoSave::on_focus_lost(state);
+                Ok(Some(InputAction::Continue))
+            }
             _ => Ok(Some(InputAction::Continue)),
         }
     }
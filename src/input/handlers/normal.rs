@@ -1,70 +1,67 @@
 use crate::core::cursor::CursorMovement;
 use crate::features::editing::{DeleteFeature, InsertFeature};
-use crate::features::file_operations::SaveFile;
+use super::structure::{self, Motion};
+use super::{brackets, comment, explorer, file_actions, finder, history, pager, search};
 use crate::input::InputAction;
-use crate::state::{EditorMode, EditorState};
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crate::state::{Confirmation, EditorMode, EditorState, PendingAction};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::io;
 
 pub fn handle_normal_mode(
     key: KeyEvent,
     state: &mut EditorState,
 ) -> io::Result<Option<InputAction>> {
+    if state.pager().is_some()
+        && state.document().is_read_only()
+        && let Some(action) = pager::handle_pager_key(&key, state)
+    {
+        return Ok(Some(action));
+    }
+    if is_edit_key(&key) && state.refuse_read_only() {
+        return Ok(Some(InputAction::Continue));
+    }
+    if let Some(motion) = Motion::for_key(&key) {
+        structure::apply(state, motion);
+        return Ok(Some(InputAction::Continue));
+    }
+    if let Some(selection) = state.selection().copied() {
+        let pairs = state.auto_pairs();
+        if let KeyCode::Char(c) = key.code
+            && !key.modifiers.contains(KeyModifiers::CONTROL)
+            && let Some(close) = pairs.closer(c)
+        {
+            let Some((buffer, cursor)) = state.buffer_and_cursor_mut() else {
+                return Ok(Some(InputAction::Continue));
+            };
+            let wrapped = InsertFeature::wrap_selection(buffer, cursor, &selection, c, close);
+            state.set_selection(Some(wrapped));
+            return Ok(Some(InputAction::Continue));
+        }
+        if !acts_on_selection(&key) {
+            state.set_selection(None);
+        }
+        if is_edit_key(&key)
+            && let Some((buffer, cursor)) = state.buffer_and_cursor_mut()
+        {
+            DeleteFeature::delete_selection(buffer, cursor, &selection);
+            if matches!(key.code, KeyCode::Backspace | KeyCode::Delete) {
+                return Ok(Some(InputAction::Continue));
+            }
+        }
+    }
+
     match key.code {
         KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             if state.document().is_modified() {
-                state.message_mut().set(
-                    "Unsaved changes! Press Ctrl+Q again to quit without saving, or Ctrl+S to save"
-                        .to_string(),
-                );
-                if let Event::Key(confirm_key) = crossterm::event::read()? {
-                    if confirm_key.kind == KeyEventKind::Press {
-                        match confirm_key.code {
-                            KeyCode::Char('q')
-                            if confirm_key.modifiers.contains(KeyModifiers::CONTROL) =>
-                                {
-                                    return Ok(Some(InputAction::Quit));
-                                }
-                            KeyCode::Char('s')
-                            if confirm_key.modifiers.contains(KeyModifiers::CONTROL) =>
-                                {
-                                    if let Err(e) = SaveFile::execute(state.document_mut()) {
-                                        state
-                                            .message_mut()
-                                            .set(format!("Save failed: {}", e));
-                                    } else {
-                                        return Ok(Some(InputAction::Quit));
-                                    }
-                                }
-                            _ => {
-                                state.message_mut().set("Quit canceled".to_string());
-                            }
-                        }
-                    }
-                }
+                let file_name = state.document().file_name();
+                state.confirm(Confirmation::unsaved_changes(&file_name, PendingAction::Quit));
             } else {
                 return Ok(Some(InputAction::Quit));
             }
         }
         KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            let has_file_path = state.document().file_path().is_some();
-            if !has_file_path {
-                state.set_mode(EditorMode::SaveAs);
-                state.clear_command_input();
-                state
-                    .message_mut()
-                    .set("Enter file path to save as:".to_string());
-            } else {
-                let file_name = state.document().file_name();
-                if let Err(e) = SaveFile::execute(state.document_mut()) {
-                    state.message_mut().set(format!("Save failed: {}", e));
-                } else {
-                    state
-                        .message_mut()
-                        .set(format!("Saved: {}", file_name));
-                    return Ok(Some(InputAction::Save));
-                }
-            }
+            let saved = file_actions::save_current(state);
+            return Ok(Some(if saved { InputAction::Save } else { InputAction::Continue }));
         }
         KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             state.set_mode(EditorMode::CommandPalette);
@@ -73,34 +70,68 @@ pub fn handle_normal_mode(
             state.set_filtered_commands(all_commands);
         }
         KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            state.set_mode(EditorMode::OpenFile);
-            state.clear_command_input();
-            state
-                .message_mut()
-                .set("Enter file path to open:".to_string());
+            file_actions::prompt_open(state);
         }
-        KeyCode::Char(c) => {
-            let (buffer, cursor, viewport) = state.buffer_cursor_and_viewport_mut();
-            InsertFeature::insert_char(buffer, cursor, c);
-            viewport.adjust_for_cursor(cursor.line);
-            state.message_mut().clear();
+        KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            finder::open_finder(state);
+        }
+        KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            search::prompt_search(state);
+        }
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            search::prompt_goto(state);
+        }
+        // Without key disambiguation terminals send Ctrl+] as Ctrl+5.
+        KeyCode::Char(']' | '5') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            brackets::jump_to_match(state);
+        }
+        // Without key disambiguation terminals send Ctrl+/ as Ctrl+7.
+        KeyCode::Char('/' | '7') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            comment::toggle_line_comment(state);
+        }
+        KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            history::undo(state);
+        }
+        KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            history::redo(state);
+        }
+        KeyCode::F(3) => {
+            search::search_next(state, !key.modifiers.contains(KeyModifiers::SHIFT));
+        }
+        KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            explorer::toggle_explorer(state);
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let pairs = state.auto_pairs();
+            if let Some((buffer, cursor, viewport)) = state.buffer_cursor_and_viewport_mut() {
+                InsertFeature::type_char(buffer, cursor, c, &pairs);
+                viewport.adjust_for_cursor(cursor.line);
+                state.message_mut().clear();
+            }
         }
         KeyCode::Enter => {
-            let (buffer, cursor, viewport) = state.buffer_cursor_and_viewport_mut();
-            InsertFeature::insert_newline(buffer, cursor);
-            viewport.adjust_for_cursor(cursor.line);
-            state.message_mut().clear();
+            let indentation = state.indentation();
+            let pairs = state.bracket_pairs().clone();
+            let indent_after = state.grammar().map_or(&[][..], |g| g.indent_after);
+            if let Some((buffer, cursor, viewport)) = state.buffer_cursor_and_viewport_mut() {
+                InsertFeature::insert_newline(buffer, cursor, &indentation, &pairs, indent_after);
+                viewport.adjust_for_cursor(cursor.line);
+                state.message_mut().clear();
+            }
         }
         KeyCode::Backspace => {
-            let (buffer, cursor, viewport) = state.buffer_cursor_and_viewport_mut();
-            DeleteFeature::backspace(buffer, cursor);
-            viewport.adjust_for_cursor(cursor.line);
-            state.message_mut().clear();
+            let pairs = state.auto_pairs();
+            if let Some((buffer, cursor, viewport)) = state.buffer_cursor_and_viewport_mut() {
+                DeleteFeature::backspace(buffer, cursor, &pairs);
+                viewport.adjust_for_cursor(cursor.line);
+                state.message_mut().clear();
+            }
         }
         KeyCode::Delete => {
-            let (buffer, cursor) = state.buffer_and_cursor_mut();
-            DeleteFeature::delete(buffer, cursor);
-            state.message_mut().clear();
+            if let Some((buffer, cursor)) = state.buffer_and_cursor_mut() {
+                DeleteFeature::delete(buffer, cursor);
+                state.message_mut().clear();
+            }
         }
         KeyCode::Left => {
             let (buffer, cursor, viewport) = state.buffer_cursor_viewport();
@@ -142,12 +173,44 @@ pub fn handle_normal_mode(
             viewport.adjust_for_cursor(cursor.line);
         }
         KeyCode::Tab => {
-            let (buffer, cursor) = state.buffer_and_cursor_mut();
-            InsertFeature::insert_tab(buffer, cursor);
-            state.message_mut().clear();
+            let indentation = state.indentation();
+            if let Some((buffer, cursor)) = state.buffer_and_cursor_mut() {
+                InsertFeature::insert_tab(buffer, cursor, &indentation);
+                state.message_mut().clear();
+            }
         }
         _ => {}
     }
 
     Ok(Some(InputAction::Continue))
-}
\ No newline at end of file
+}
+
+pub(super) fn handle_paste(text: &str, state: &mut EditorState) {
+    if let Some(selection) = state.selection().copied()
+        && let Some((buffer, cursor)) = state.buffer_and_cursor_mut()
+    {
+        DeleteFeature::delete_selection(buffer, cursor, &selection);
+        state.set_selection(None);
+    }
+    let Some((buffer, cursor, viewport)) = state.buffer_cursor_and_viewport_mut() else {
+        return;
+    };
+    InsertFeature::insert_text(buffer, cursor, text);
+    viewport.adjust_for_cursor(cursor.line);
+    state.message_mut().clear();
+}
+
+/// Keys that use the selection rather than clear it. The palette keeps it
+/// for commands that do.
+fn acts_on_selection(key: &KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL)
+        && matches!(key.code, KeyCode::Char('p' | '/' | '7'))
+}
+
+fn is_edit_key(key: &KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Char(_) => !key.modifiers.contains(KeyModifiers::CONTROL),
+        KeyCode::Enter | KeyCode::Backspace | KeyCode::Delete | KeyCode::Tab => true,
+        _ => false,
+    }
+}
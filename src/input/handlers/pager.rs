@@ -0,0 +1,89 @@
+use super::search;
+use crate::core::cursor::CursorMovement;
+use crate::features::pager::PagerSource;
+use crate::input::InputAction;
+use crate::state::{Confirmation, EditorState, PendingAction};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// less-style keys for a read-only pager document. Returns `None` when the key
+/// should fall through to the normal editor bindings.
+pub(super) fn handle_pager_key(key: &KeyEvent, state: &mut EditorState) -> Option<InputAction> {
+    if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+        return None;
+    }
+
+    match key.code {
+        KeyCode::Char('q') => {
+            if state.document().is_modified() {
+                let file_name = state.document().file_name();
+                state.confirm(Confirmation::unsaved_changes(&file_name, PendingAction::Quit));
+            } else {
+                return Some(InputAction::Quit);
+            }
+        }
+        KeyCode::Char(' ') | KeyCode::Char('f') => page(state, true),
+        KeyCode::Char('b') => page(state, false),
+        KeyCode::Char('j') => line(state, true),
+        KeyCode::Char('k') => line(state, false),
+        KeyCode::Char('g') => search::goto_line(state, 0, 0),
+        KeyCode::Char('G') => PagerSource::scroll_to_end(state),
+        KeyCode::Char('/') => search::prompt_search(state),
+        KeyCode::Char('n') => search::search_next(state, true),
+        KeyCode::Char('N') => search::search_next(state, false),
+        KeyCode::Char(':') => search::prompt_goto(state),
+        KeyCode::Char('F') => {
+            let follow = state.pager().is_some_and(|p| !p.follow);
+            if let Some(pager) = state.pager_mut() {
+                pager.follow = follow;
+            }
+            if follow {
+                PagerSource::scroll_to_end(state);
+                state
+                    .message_mut()
+                    .set("Following new input (F to stop)".to_string());
+            } else {
+                state.message_mut().set("Stopped following".to_string());
+            }
+        }
+        KeyCode::Up | KeyCode::PageUp | KeyCode::Home => {
+            stop_following(state);
+            return None;
+        }
+        KeyCode::Char(_) => {}
+        _ => return None,
+    }
+    Some(InputAction::Continue)
+}
+
+fn page(state: &mut EditorState, down: bool) {
+    if !down {
+        stop_following(state);
+    }
+    let (buffer, cursor, viewport) = state.buffer_cursor_viewport();
+    let height = viewport.height();
+    if down {
+        CursorMovement::page_down(cursor, buffer, height);
+    } else {
+        CursorMovement::page_up(cursor, buffer, height);
+    }
+    viewport.adjust_for_cursor(cursor.line);
+}
+
+fn line(state: &mut EditorState, down: bool) {
+    if !down {
+        stop_following(state);
+    }
+    let (buffer, cursor, viewport) = state.buffer_cursor_viewport();
+    if down {
+        CursorMovement::move_down(cursor, buffer);
+    } else {
+        CursorMovement::move_up(cursor, buffer);
+    }
+    viewport.adjust_for_cursor(cursor.line);
+}
+
+fn stop_following(state: &mut EditorState) {
+    if let Some(pager) = state.pager_mut() {
+        pager.follow = false;
+    }
+}
@@ -0,0 +1,83 @@
+use super::file_actions;
+use crate::features::session::{RecentFiles, RecentList};
+use crate::input::InputAction;
+use crate::state::{Confirmation, EditorMode, EditorState, PendingAction};
+use crate::ui::components::FieldEdit;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::io;
+
+pub(super) fn open_recent(state: &mut EditorState) {
+    let list = RecentList::new(RecentFiles::list());
+    if list.is_empty() {
+        state.message_mut().set("No recent files".to_string());
+        return;
+    }
+    state.set_recent_files(list);
+    state.clear_command_input();
+    state.set_mode(EditorMode::RecentFiles);
+    state
+        .message_mut()
+        .set("Enter: open | Ctrl+D: remove from list | Esc: cancel".to_string());
+}
+
+pub fn handle_recent_mode(
+    key: KeyEvent,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    if !(ctrl && key.code == KeyCode::Char('d')) {
+        match state.command_field_mut().handle_key(&key) {
+            FieldEdit::Changed => {
+                let query = state.command_input().to_string();
+                state.recent_files_mut().set_query(&query);
+                return Ok(Some(InputAction::Continue));
+            }
+            FieldEdit::CursorMoved => return Ok(Some(InputAction::Continue)),
+            FieldEdit::Unhandled => {}
+        }
+    }
+
+    match key.code {
+        KeyCode::Up => state.recent_files_mut().select_previous(),
+        KeyCode::Down => state.recent_files_mut().select_next(),
+        KeyCode::Char('p') if ctrl => state.recent_files_mut().select_previous(),
+        KeyCode::Char('n') if ctrl => state.recent_files_mut().select_next(),
+        KeyCode::Char('d') if ctrl => {
+            if let Some(path) = state.recent_files_mut().remove_selected() {
+                match RecentFiles::remove(&path) {
+                    Ok(()) => state
+                        .message_mut()
+                        .set(format!("Removed from recent files: {}", path.display())),
+                    Err(e) => state
+                        .message_mut()
+                        .set(format!("Cannot update recent files: {}", e)),
+                }
+            }
+        }
+        KeyCode::Enter => {
+            let Some(path) = state.recent_files().selected_path() else {
+                return Ok(Some(InputAction::Continue));
+            };
+            let path = path.to_string_lossy().into_owned();
+            state.set_mode(EditorMode::Normal);
+            state.clear_command_input();
+            if state.document().is_modified() {
+                let file_name = state.document().file_name();
+                state.confirm(Confirmation::unsaved_changes(
+                    &file_name,
+                    PendingAction::OpenFile(path),
+                ));
+            } else {
+                file_actions::open(state, &path);
+            }
+        }
+        KeyCode::Esc => {
+            state.set_mode(EditorMode::Normal);
+            state.clear_command_input();
+            state.message_mut().clear();
+        }
+        _ => {}
+    }
+
+    Ok(Some(InputAction::Continue))
+}
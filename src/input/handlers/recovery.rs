@@ -0,0 +1,61 @@
+use crate::features::file_operations::RecoverFile;
+use crate::input::InputAction;
+use crate::state::{EditorMode, EditorState};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::io;
+
+pub fn handle_recovery_mode(
+    key: KeyEvent,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    match key.code {
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            if let Some(recovery) = state.take_recovery() {
+                RecoverFile::recover(state.document_mut(), &recovery);
+                state
+                    .message_mut()
+                    .set(format!("Recovered: {}", recovery.file_path.display()));
+            }
+            state.set_mode(EditorMode::Normal);
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            if let Some(recovery) = state.recovery_mut() {
+                recovery.show_diff = !recovery.show_diff;
+                recovery.scroll = 0;
+            }
+        }
+        KeyCode::Char('x') | KeyCode::Char('X') | KeyCode::Delete => {
+            if let Some(recovery) = state.take_recovery() {
+                match RecoverFile::discard(&recovery) {
+                    Ok(_) => state.message_mut().set("Swap file discarded".to_string()),
+                    Err(e) => state
+                        .message_mut()
+                        .set(format!("Error removing swap file: {}", e)),
+                }
+            }
+            state.set_mode(EditorMode::Normal);
+        }
+        KeyCode::Up => {
+            if let Some(recovery) = state.recovery_mut() {
+                recovery.scroll = recovery.scroll.saturating_sub(1);
+            }
+        }
+        KeyCode::Down => {
+            if let Some(recovery) = state.recovery_mut()
+                && recovery.scroll + 1 < recovery.diff.len()
+            {
+                recovery.scroll += 1;
+            }
+        }
+        KeyCode::Esc | KeyCode::Char('i') | KeyCode::Char('I') => {
+            state.take_recovery();
+            state.set_mode(EditorMode::Normal);
+            state
+                .message_mut()
+                .set("Swap file kept; editing the file on disk".to_string());
+        }
+        _ => {}
+    }
+
+    Ok(Some(InputAction::Continue))
+}
@@ -0,0 +1,101 @@
+use crate::features::navigation::{GotoFeature, SearchFeature};
+use crate::input::InputAction;
+use crate::state::{EditorMode, EditorState};
+use crate::ui::components::FieldEdit;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::io;
+
+pub(super) fn prompt_search(state: &mut EditorState) {
+    state.set_mode(EditorMode::Search);
+    let last = state.last_search().unwrap_or_default().to_string();
+    state.set_command_input(last);
+    state.set_path_candidates(Vec::new());
+}
+
+pub(super) fn prompt_goto(state: &mut EditorState) {
+    state.set_mode(EditorMode::GotoLine);
+    state.clear_command_input();
+    state.set_path_candidates(Vec::new());
+    let lines = state.document().buffer().len_lines();
+    state
+        .message_mut()
+        .set(format!("Line number (1-{}), optionally LINE:COL", lines));
+}
+
+pub(super) fn search_next(state: &mut EditorState, forward: bool) {
+    let Some(query) = state.last_search().map(str::to_string) else {
+        state.message_mut().set("No previous search".to_string());
+        return;
+    };
+    let found = SearchFeature::find(state.document().buffer(), state.cursor(), &query, forward);
+    match found {
+        Some(position) => {
+            let (cursor, viewport) = state.cursor_and_viewport_mut();
+            *cursor = position;
+            viewport.adjust_for_cursor(cursor.line);
+            state.message_mut().set(format!("/{}", query));
+        }
+        None => state.message_mut().set(format!("Not found: {}", query)),
+    }
+}
+
+pub(super) fn goto_line(state: &mut EditorState, line: usize, column: usize) {
+    let (buffer, cursor, viewport) = state.buffer_cursor_viewport();
+    GotoFeature::goto(cursor, buffer, line, column);
+    viewport.adjust_for_cursor(cursor.line);
+}
+
+pub fn handle_prompt_mode(
+    key: KeyEvent,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    match state.command_field_mut().handle_key(&key) {
+        FieldEdit::Changed | FieldEdit::CursorMoved => return Ok(Some(InputAction::Continue)),
+        FieldEdit::Unhandled => {}
+    }
+
+    match key.code {
+        KeyCode::Enter => {
+            let input = state.command_input().to_string();
+            let mode = state.mode().clone();
+            state.set_mode(EditorMode::Normal);
+            state.clear_command_input();
+            state.message_mut().clear();
+            match mode {
+                EditorMode::Search if !input.is_empty() => {
+                    state.set_last_search(input);
+                    search_next(state, true);
+                }
+                EditorMode::GotoLine => match parse_position(&input) {
+                    Some((line, column)) => goto_line(state, line, column),
+                    None if input.is_empty() => {}
+                    None => state
+                        .message_mut()
+                        .set(format!("Not a line number: {}", input)),
+                },
+                _ => {}
+            }
+        }
+        KeyCode::Esc => {
+            state.set_mode(EditorMode::Normal);
+            state.clear_command_input();
+            state.message_mut().clear();
+        }
+        _ => {}
+    }
+
+    Ok(Some(InputAction::Continue))
+}
+
+fn parse_position(input: &str) -> Option<(usize, usize)> {
+    let (line, column) = match input.trim().split_once(':') {
+        Some((line, column)) => (line, Some(column)),
+        None => (input.trim(), None),
+    };
+    let line = line.parse::<usize>().ok()?.saturating_sub(1);
+    let column = match column {
+        Some(column) => column.parse::<usize>().ok()?.saturating_sub(1),
+        None => 0,
+    };
+    Some((line, column))
+}
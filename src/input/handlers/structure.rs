@@ -0,0 +1,73 @@
+use crate::core::cursor::Selection;
+use crate::state::EditorState;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::ops::Range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Motion {
+    Expand,
+    Shrink,
+    Parent,
+    NextSibling,
+    PreviousSibling,
+}
+
+impl Motion {
+    pub(super) fn for_key(key: &KeyEvent) -> Option<Self> {
+        if !key.modifiers.contains(KeyModifiers::ALT) {
+            return None;
+        }
+        match key.code {
+            KeyCode::Up => Some(Self::Expand),
+            KeyCode::Down => Some(Self::Shrink),
+            KeyCode::Right => Some(Self::NextSibling),
+            KeyCode::Left => Some(Self::PreviousSibling),
+            KeyCode::Char('p') => Some(Self::Parent),
+            _ => None,
+        }
+    }
+}
+
+/// Expanding selects the enclosing node; the other moves select the node
+/// they land on, with the cursor at its start.
+pub(super) fn apply(state: &mut EditorState, motion: Motion) {
+    if motion == Motion::Shrink {
+        state.shrink_selection();
+        return;
+    }
+    let current = state.selection_range();
+    let Some(target) = find(state, motion, current) else {
+        return;
+    };
+    let selection = Selection::from_char_range(state.document().buffer(), target);
+    match motion {
+        Motion::Expand => state.expand_selection(selection),
+        _ => state.set_selection(Some(selection)),
+    }
+}
+
+#[cfg(feature = "tree-sitter")]
+fn find(state: &mut EditorState, motion: Motion, current: Range<usize>) -> Option<Range<usize>> {
+    let buffer = state.document().buffer();
+    let Some(tree) = state.syntax_tree() else {
+        state
+            .message_mut()
+            .set("No syntax tree for this file type".to_string());
+        return None;
+    };
+    match motion {
+        Motion::Expand => tree.expand(buffer, current),
+        Motion::Parent => tree.parent(buffer, current),
+        Motion::NextSibling => tree.next_sibling(buffer, current),
+        Motion::PreviousSibling => tree.previous_sibling(buffer, current),
+        Motion::Shrink => None,
+    }
+}
+
+#[cfg(not(feature = "tree-sitter"))]
+fn find(state: &mut EditorState, _motion: Motion, _current: Range<usize>) -> Option<Range<usize>> {
+    state
+        .message_mut()
+        .set("Structural selection needs a build with --features tree-sitter".to_string());
+    None
+}
@@ -0,0 +1,78 @@
+use crate::input::InputAction;
+use crate::state::{EditorMode, EditorState};
+use crate::ui::{ThemeLoader, ThemePicker};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::io;
+
+pub(super) fn open_theme_picker(state: &mut EditorState) {
+    let picker = ThemePicker::new(ThemeLoader::names(), state.theme().clone());
+    state.set_theme_picker(Some(picker));
+    state.set_mode(EditorMode::SelectTheme);
+    state
+        .message_mut()
+        .set("Up/Down: preview | Enter: apply | Esc: cancel".to_string());
+}
+
+pub fn handle_theme_mode(
+    key: KeyEvent,
+    state: &mut EditorState,
+) -> io::Result<Option<InputAction>> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let Some(picker) = state.theme_picker_mut() else {
+        state.set_mode(EditorMode::Normal);
+        return Ok(Some(InputAction::Continue));
+    };
+
+    match key.code {
+        KeyCode::Up => picker.select_previous(),
+        KeyCode::Down => picker.select_next(),
+        KeyCode::Char('p') if ctrl => picker.select_previous(),
+        KeyCode::Char('n') if ctrl => picker.select_next(),
+        KeyCode::Enter => {
+            let name = state.theme().name.clone();
+            close(state);
+            state.message_mut().set(format!(
+                "Theme: {} (set ui.theme in config.toml to keep it)",
+                name
+            ));
+            return Ok(Some(InputAction::Continue));
+        }
+        KeyCode::Esc => {
+            if let Some(picker) = state.theme_picker() {
+                let original = picker.original().clone();
+                state.set_theme(original);
+            }
+            close(state);
+            state.message_mut().clear();
+            return Ok(Some(InputAction::Continue));
+        }
+        _ => return Ok(Some(InputAction::Continue)),
+    }
+
+    preview(state);
+    Ok(Some(InputAction::Continue))
+}
+
+fn preview(state: &mut EditorState) {
+    let Some(name) = state
+        .theme_picker()
+        .and_then(|p| p.selected_name())
+        .map(str::to_string)
+    else {
+        return;
+    };
+    match ThemeLoader::load(&name) {
+        Ok(theme) => {
+            state.set_theme(theme);
+            state
+                .message_mut()
+                .set("Up/Down: preview | Enter: apply | Esc: cancel".to_string());
+        }
+        Err(e) => state.message_mut().set(format!("Cannot load theme: {}", e)),
+    }
+}
+
+fn close(state: &mut EditorState) {
+    state.set_theme_picker(None);
+    state.set_mode(EditorMode::Normal);
+}
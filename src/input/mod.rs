@@ -3,4 +3,5 @@ mod keybindings;
 mod handlers;
 
 pub use events::InputAction;
+pub use keybindings::Keybindings;
 pub use handlers::InputHandler;
\ No newline at end of file
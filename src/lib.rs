@@ -1,3 +1,5 @@
+pub mod app;
+pub mod config;
 pub mod core;
 pub mod features;
 pub mod state;
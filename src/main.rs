@@ -1,55 +1,245 @@
 use crossterm::{
-    event,
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use kappa::{EditorState, InputAction, InputHandler};
-use kappa::core::document::{Document, FileIO};
+use kappa::{EditorMode, EditorState};
+use kappa::app::{CliCommand, CliOptions, EventLoop, FileArg, TaskSpawner, USAGE};
+use kappa::config::{Config, ConfigLoader};
+use kappa::core::buffer::RopeBuffer;
+use kappa::core::document::{Document, SwapFile};
+use kappa::features::file_operations::{ArgFile, ArgList, LargeFileSettings, OpenFile};
+use kappa::features::navigation::GotoFeature;
+use kappa::features::pager::{PagerSource, PagerState};
+use kappa::features::session::{FilePositions, RecentFiles, RecentList, Session};
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::env;
-use std::io;
+use std::io::{self, IsTerminal, Read};
+use std::process::ExitCode;
+
+const EXIT_USAGE: u8 = 2;
+
+fn main() -> ExitCode {
+    let options = match CliOptions::parse(env::args().skip(1)) {
+        Ok(CliCommand::Run(options)) => options,
+        Ok(CliCommand::Help) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Ok(CliCommand::Version) => {
+            println!("kappa {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("kappa: {}", e);
+            eprintln!("Try 'kappa --help' for more information.");
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    match run(options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("kappa: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(options: CliOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let event_loop = EventLoop::new();
+    let loaded = ConfigLoader::load(&env::current_dir().unwrap_or_default());
+    let mut state = initial_state(&options, loaded.config.clone(), &event_loop.spawner())?;
+    state.set_theme(loaded.theme.clone());
+    if state.config().ui.explorer
+        && state.pager().is_none()
+        && !state.is_explorer_visible()
+        && let Ok(dir) = env::current_dir()
+    {
+        state.explorer_mut().set_root(dir);
+        state.set_explorer_visible(true);
+    }
+    if let Some(message) = loaded.error_message() {
+        state.message_mut().set(message);
+    }
+
+    install_panic_hook();
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableFocusChange,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let args: Vec<String> = env::args().collect();
-    let document = if let Some(filename) = args.get(1) {
-        FileIO::load_from_file(filename)?
-    } else {
-        Document::new()
-    };
+    state
+        .viewport_mut()
+        .set_height((terminal.size()?.height as usize).saturating_sub(2));
+    let line = state.cursor().line;
+    state.viewport_mut().adjust_for_cursor(line);
+    state.check_recovery();
 
-    let mut state = EditorState::new(document);
-    state.viewport_mut().set_height(terminal.size()?.height as usize - 2);
-
-    let result = run_app(&mut terminal, &mut state);
+    if state.pager().is_some() {
+        PagerSource::spawn(io::stdin(), &event_loop.spawner());
+    }
+    let result = event_loop.run(&mut terminal, &mut state);
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        DisableFocusChange,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
 
-    result
+    Ok(result?)
+}
+
+/// Loads the first file argument before the terminal is taken over, so load
+/// errors are reported on a normal screen.
+fn initial_state(
+    options: &CliOptions,
+    config: Config,
+    tasks: &TaskSpawner,
+) -> io::Result<EditorState> {
+    let settings = LargeFileSettings::default();
+    if options.session {
+        return session_state(options, config, settings, tasks);
+    }
+
+    let first = options.files.first();
+    let (mut document, message, mut loading) = match first {
+        Some(FileArg { path: None, .. }) => {
+            // crossterm reads keys from /dev/tty when stdin is not a terminal.
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)?;
+            let mut document = Document::from_buffer(RopeBuffer::from_string(&text), None);
+            document.mark_modified();
+            (document, None, None)
+        }
+        Some(FileArg {
+            path: Some(path), ..
+        }) => {
+            let opened = OpenFile::open_with(&path.to_string_lossy(), &settings, Some(tasks))
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+            let message = opened.created.then(|| format!("New file: {}", path.display()));
+            (opened.document, message, opened.loading)
+        }
+        None => (Document::new(), None, None),
+    };
+    if options.read_only {
+        document.set_read_only(true);
+        if let Some(progress) = &mut loading {
+            progress.read_only = true;
+        }
+    }
+
+    let show_recent = options.recent
+        || (config.ui.recent_on_start && options.files.is_empty() && io::stdin().is_terminal());
+    let mut state = EditorState::with_config(document, config);
+    state.set_large_files(settings);
+    state.set_loading(loading);
+    if let Some(message) = message {
+        state.message_mut().set(message);
+    }
+    // Piped input with no files to open turns kappa into a pager.
+    if options.files.is_empty() && !io::stdin().is_terminal() {
+        state.document_mut().set_read_only(true);
+        state.set_pager(Some(PagerState::new()));
+        state.message_mut().set(
+            "Reading input | q: quit | /: search | :: go to line | F: follow".to_string(),
+        );
+    }
+    if show_recent {
+        let list = RecentList::new(RecentFiles::list());
+        if list.is_empty() {
+            state.message_mut().set("No recent files".to_string());
+        } else {
+            state.set_recent_files(list);
+            state.set_mode(EditorMode::RecentFiles);
+            state
+                .message_mut()
+                .set("Enter: open | Ctrl+D: remove from list | Esc: cancel".to_string());
+        }
+    }
+    let files = options
+        .files
+        .iter()
+        .filter_map(|f| {
+            Some(ArgFile {
+                path: f.path.clone()?,
+                position: f.position(),
+            })
+        })
+        .collect();
+    state.set_arg_list(ArgList::new(files, options.read_only));
+
+    if first.is_some_and(|f| f.path.is_none()) {
+        if let Some((line, column)) = first.and_then(FileArg::position) {
+            let (buffer, cursor) = state.buffer_cursor();
+            GotoFeature::goto(cursor, buffer, line, column);
+        }
+    } else if first.is_some() && !ArgList::apply_position(&mut state) {
+        FilePositions::restore(&mut state);
+    }
+    if options.files.len() > 1 {
+        let count = options.files.len();
+        state
+            .message_mut()
+            .set(format!("{} files; use Next File / Previous File to switch", count));
+    }
+    Ok(state)
 }
 
-fn run_app<B: ratatui::backend::Backend>(
-    terminal: &mut Terminal<B>,
-    state: &mut EditorState,
-) -> Result<(), Box<dyn std::error::Error>> {
-    loop {
-        terminal.draw(|f| kappa::ui::render(f, state))?;
-
-        let event = event::read()?;
-        if let Some(action) = InputHandler::handle(event, state)? {
-            match action {
-                InputAction::Quit => break,
-                InputAction::Save => {}
-                InputAction::Continue => {}
+fn session_state(
+    options: &CliOptions,
+    config: Config,
+    settings: LargeFileSettings,
+    tasks: &TaskSpawner,
+) -> io::Result<EditorState> {
+    let dir = env::current_dir()?;
+    let mut state = EditorState::with_config(Document::new(), config);
+    state.set_large_files(settings);
+    state.set_tasks(tasks.clone());
+    match Session::load(&dir)? {
+        Some(session) => {
+            session.restore(&mut state)?;
+            if options.read_only {
+                state.document_mut().set_read_only(true);
             }
+            state
+                .message_mut()
+                .set(format!("Session restored ({} files)", session.files.len()));
         }
+        None => state
+            .message_mut()
+            .set(format!("No saved session for {}", dir.display())),
     }
-    Ok(())
-}
\ No newline at end of file
+    Ok(state)
+}
+
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        SwapFile::flush_crash_snapshot();
+        let _ = disable_raw_mode();
+        let _ = execute!(
+            io::stdout(),
+            DisableBracketedPaste,
+            DisableMouseCapture,
+            DisableFocusChange,
+            LeaveAlternateScreen
+        );
+        default_hook(info);
+    }));
+}
@@ -0,0 +1,190 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingAction {
+    Quit,
+    NewFile,
+    CloseFile,
+    OpenFile(String),
+    SaveAs(String),
+    Reload,
+    DeletePath(PathBuf),
+    MovePath(PathBuf, PathBuf),
+    RestoreSession,
+}
+
+impl PendingAction {
+    pub fn is_explorer(&self) -> bool {
+        matches!(self, PendingAction::DeletePath(_) | PendingAction::MovePath(..))
+    }
+}
+
+pub struct Confirmation {
+    pub dialog: ConfirmationDialog,
+    pub action: PendingAction,
+}
+
+impl Confirmation {
+    pub fn unsaved_changes(file_name: &str, action: PendingAction) -> Self {
+        let verb = match &action {
+            PendingAction::Quit => "quitting",
+            PendingAction::OpenFile(_) => "opening another file",
+            _ => "closing it",
+        };
+        Self {
+            dialog: ConfirmationDialog::new(
+                "Unsaved Changes",
+                format!("Save changes to {} before {}?", file_name, verb),
+                vec![
+                    ConfirmChoice::Save,
+                    ConfirmChoice::Discard,
+                    ConfirmChoice::Cancel,
+                ],
+            ),
+            action,
+        }
+    }
+
+    pub fn overwrite(path: &str) -> Self {
+        Self {
+            dialog: ConfirmationDialog::new(
+                "File Exists",
+                format!("{} already exists. Overwrite it?", path),
+                vec![ConfirmChoice::Overwrite, ConfirmChoice::Cancel],
+            ),
+            action: PendingAction::SaveAs(path.to_string()),
+        }
+    }
+
+    pub fn reload(file_name: &str) -> Self {
+        Self {
+            dialog: ConfirmationDialog::new(
+                "File Changed on Disk",
+                format!(
+                    "{} was changed by another program. Reload it and lose your edits?",
+                    file_name
+                ),
+                vec![ConfirmChoice::Reload, ConfirmChoice::Keep],
+            ),
+            action: PendingAction::Reload,
+        }
+    }
+
+    pub fn delete(path: PathBuf, display: &str) -> Self {
+        let what = if path.is_dir() {
+            "the directory and everything in it"
+        } else {
+            "the file"
+        };
+        Self {
+            dialog: ConfirmationDialog::new(
+                "Delete",
+                format!("Delete {}? This removes {} from disk.", display, what),
+                vec![ConfirmChoice::Delete, ConfirmChoice::Cancel],
+            ),
+            action: PendingAction::DeletePath(path),
+        }
+    }
+
+    pub fn move_over(from: PathBuf, to: PathBuf, display: &str) -> Self {
+        Self {
+            dialog: ConfirmationDialog::new(
+                "File Exists",
+                format!("{} already exists. Replace it?", display),
+                vec![ConfirmChoice::Overwrite, ConfirmChoice::Cancel],
+            ),
+            action: PendingAction::MovePath(from, to),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmChoice {
+    Save,
+    Discard,
+    Cancel,
+    Overwrite,
+    Reload,
+    Keep,
+    Delete,
+}
+
+impl ConfirmChoice {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConfirmChoice::Save => "Save",
+            ConfirmChoice::Discard => "Discard",
+            ConfirmChoice::Cancel => "Cancel",
+            ConfirmChoice::Overwrite => "Overwrite",
+            ConfirmChoice::Reload => "Reload",
+            ConfirmChoice::Keep => "Keep Mine",
+            ConfirmChoice::Delete => "Delete",
+        }
+    }
+
+    fn shortcut(&self) -> char {
+        self.label()
+            .chars()
+            .next()
+            .unwrap_or(' ')
+            .to_ascii_lowercase()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmationDialog {
+    title: String,
+    message: String,
+    buttons: Vec<ConfirmChoice>,
+    selected: usize,
+}
+
+impl ConfirmationDialog {
+    pub fn new(title: &str, message: String, buttons: Vec<ConfirmChoice>) -> Self {
+        Self {
+            title: title.to_string(),
+            message,
+            buttons,
+            selected: 0,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn buttons(&self) -> &[ConfirmChoice] {
+        &self.buttons
+    }
+
+    pub fn selected(&self) -> ConfirmChoice {
+        self.buttons[self.selected]
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.buttons.len();
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = (self.selected + self.buttons.len() - 1) % self.buttons.len();
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.buttons.len() {
+            self.selected = index;
+        }
+    }
+
+    pub fn choice_for_key(&self, ch: char) -> Option<ConfirmChoice> {
+        let ch = ch.to_ascii_lowercase();
+        self.buttons.iter().copied().find(|b| b.shortcut() == ch)
+    }
+
+    pub fn cancel_choice(&self) -> ConfirmChoice {
+        *self.buttons.last().unwrap_or(&ConfirmChoice::Cancel)
+    }
+}
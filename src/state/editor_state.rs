@@ -1,8 +1,25 @@
-use crate::core::cursor::CursorPosition;
-use crate::core::document::Document;
+use crate::app::TaskSpawner;
+use crate::config::Config;
+use crate::core::cursor::{CursorPosition, Selection};
+use crate::core::document::{BackupEntry, BackupPolicy, Document, Indentation, SwapFile};
 use crate::core::viewport::Viewport;
 use crate::features::command_palette::{Command, CommandRegistry};
-use crate::state::{EditorMode, MessageState};
+use crate::features::editing::AutoPairs;
+use crate::features::explorer::{ExplorerPrompt, FileTree};
+use crate::features::finder::FilePicker;
+use crate::features::navigation::{BracketFeature, BracketPairs};
+use crate::features::pager::PagerState;
+use crate::features::session::{FilePositions, RecentList};
+#[cfg(feature = "tree-sitter")]
+use crate::features::syntax::SyntaxTree;
+use crate::features::syntax::{Grammar, Highlighter, Token};
+use crate::features::file_operations::{
+    ArgList, AutoSaveSettings, LargeFileSettings, LoadProgress, PathCandidate, RecoverFile, SwapRecovery,
+};
+use crate::state::{Confirmation, EditorMode, MessageState, PendingAction};
+use crate::ui::components::InputField;
+use crate::ui::{ColorSupport, Theme, ThemePicker};
+use std::ops::Range;
 
 pub struct EditorState {
     document: Document,
@@ -10,24 +27,141 @@ pub struct EditorState {
     viewport: Viewport,
     mode: EditorMode,
     message: MessageState,
-    command_input: String,
+    command_input: InputField,
     command_registry: CommandRegistry,
     filtered_commands: Vec<Command>,
+    recovery: Option<SwapRecovery>,
+    backup_policy: BackupPolicy,
+    backups: Vec<BackupEntry>,
+    selected_backup: usize,
+    auto_save: AutoSaveSettings,
+    tasks: Option<TaskSpawner>,
+    confirmation: Option<Confirmation>,
+    /// Confirmed action waiting for the Save As prompt to save the buffer.
+    after_save_as: Option<PendingAction>,
+    path_candidates: Vec<PathCandidate>,
+    selected_candidate: Option<usize>,
+    file_picker: FilePicker,
+    recent_files: RecentList,
+    explorer: FileTree,
+    explorer_visible: bool,
+    explorer_prompt: Option<ExplorerPrompt>,
+    arg_list: ArgList,
+    pager: Option<PagerState>,
+    last_search: Option<String>,
+    large_files: LargeFileSettings,
+    loading: Option<LoadProgress>,
+    config: Config,
+    theme: Theme,
+    theme_picker: Option<ThemePicker>,
+    color_support: ColorSupport,
+    highlighter: Option<Highlighter>,
+    #[cfg(feature = "tree-sitter")]
+    syntax_tree: Option<SyntaxTree>,
+    selection: Option<Selection>,
+    /// Selections replaced by structural expansion, innermost last.
+    selection_history: Vec<Selection>,
+    bracket_pairs: BracketPairs,
 }
 
 impl EditorState {
     pub fn new(document: Document) -> Self {
+        Self::with_config(document, Config::default())
+    }
+
+    pub fn with_config(document: Document, config: Config) -> Self {
         let command_registry = CommandRegistry::new();
-        Self {
+        let message = match config.ui.welcome_message.as_str() {
+            "" => MessageState::empty(),
+            welcome => MessageState::new(welcome),
+        };
+        let mut state = Self {
             document,
             cursor: CursorPosition::new(),
             viewport: Viewport::new(20),
             mode: EditorMode::Normal,
-            message: MessageState::new("Ctrl+P: Command Palette | Ctrl+S: Save | Ctrl+Q: Quit"),
-            command_input: String::new(),
+            message,
+            command_input: InputField::new(),
             command_registry,
             filtered_commands: Vec::new(),
-        }
+            recovery: None,
+            backup_policy: BackupPolicy::default(),
+            backups: Vec::new(),
+            selected_backup: 0,
+            auto_save: AutoSaveSettings::default(),
+            tasks: None,
+            confirmation: None,
+            after_save_as: None,
+            path_candidates: Vec::new(),
+            selected_candidate: None,
+            file_picker: FilePicker::new(),
+            recent_files: RecentList::default(),
+            explorer: FileTree::default(),
+            explorer_visible: false,
+            explorer_prompt: None,
+            arg_list: ArgList::default(),
+            pager: None,
+            last_search: None,
+            large_files: LargeFileSettings::default(),
+            loading: None,
+            config: Config::default(),
+            theme: Theme::default(),
+            theme_picker: None,
+            color_support: ColorSupport::TrueColor,
+            highlighter: None,
+            #[cfg(feature = "tree-sitter")]
+            syntax_tree: None,
+            selection: None,
+            selection_history: Vec::new(),
+            bracket_pairs: BracketPairs::default(),
+        };
+        state.apply_config(config);
+        state.detect_syntax();
+        state
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn color_support(&self) -> ColorSupport {
+        self.color_support
+    }
+
+    pub fn theme_picker(&self) -> Option<&ThemePicker> {
+        self.theme_picker.as_ref()
+    }
+
+    pub fn theme_picker_mut(&mut self) -> Option<&mut ThemePicker> {
+        self.theme_picker.as_mut()
+    }
+
+    pub fn set_theme_picker(&mut self, picker: Option<ThemePicker>) {
+        self.theme_picker = picker;
+    }
+
+    pub fn indentation(&self) -> Indentation {
+        let editor = &self.config.editor;
+        self.document
+            .settings()
+            .indentation(editor.tab_width, editor.insert_spaces)
+    }
+
+    pub fn apply_config(&mut self, config: Config) {
+        self.viewport.set_scroll_margin(config.editor.scroll_margin);
+        self.viewport.adjust_for_cursor(self.cursor.line);
+        self.auto_save = config.editor.auto_save_settings();
+        self.color_support = ColorSupport::for_mode(config.ui.colors);
+        self.bracket_pairs = config.editor.bracket_pairs();
+        self.config = config;
     }
 
     pub fn document(&self) -> &Document {
@@ -71,19 +205,19 @@ impl EditorState {
     }
 
     pub fn command_input(&self) -> &str {
-        &self.command_input
+        self.command_input.text()
     }
 
     pub fn set_command_input(&mut self, input: String) {
-        self.command_input = input;
+        self.command_input.set_text(input);
     }
 
-    pub fn push_command_input(&mut self, ch: char) {
-        self.command_input.push(ch);
+    pub fn command_field(&self) -> &InputField {
+        &self.command_input
     }
 
-    pub fn pop_command_input(&mut self) {
-        self.command_input.pop();
+    pub fn command_field_mut(&mut self) -> &mut InputField {
+        &mut self.command_input
     }
 
     pub fn clear_command_input(&mut self) {
@@ -103,17 +237,431 @@ impl EditorState {
     }
 
     pub fn replace_document(&mut self, document: Document) {
+        self.install_document(document);
+        self.loading = None;
+        self.check_recovery();
+    }
+
+    /// Installs a document that is still being filled in the background. The
+    /// swap check is deferred until loading finishes.
+    pub fn start_loading(&mut self, document: Document, progress: LoadProgress) {
+        self.install_document(document);
+        self.loading = Some(progress);
+    }
+
+    fn install_document(&mut self, document: Document) {
+        let _ = FilePositions::record_current(self);
+        let _ = SwapFile::remove(&mut self.document);
         self.document = document;
         self.cursor = CursorPosition::new();
         self.viewport = Viewport::new(self.viewport.height());
+        self.viewport.set_scroll_margin(self.config.editor.scroll_margin);
+        self.pager = None;
+        self.set_selection(None);
+        self.detect_syntax();
+    }
+
+    pub fn highlighter(&self) -> Option<&Highlighter> {
+        self.highlighter.as_ref()
+    }
+
+    pub fn grammar(&self) -> Option<&'static Grammar> {
+        self.highlighter.as_ref().map(|h| h.grammar())
+    }
+
+    /// Picks the grammar for the current document. Memory-mapped files are
+    /// left plain.
+    pub fn detect_syntax(&mut self) {
+        self.document.take_edits();
+        let buffer = self.document.buffer();
+        let first_line = buffer.line(0).map(String::from).unwrap_or_default();
+        self.highlighter = Grammar::detect(self.document.file_path().map(|p| p.as_path()), &first_line)
+            .filter(|_| !buffer.is_mapped())
+            .map(Highlighter::new);
+        #[cfg(feature = "tree-sitter")]
+        {
+            self.syntax_tree = self
+                .highlighter
+                .as_ref()
+                .and_then(|h| SyntaxTree::new(h.grammar()));
+        }
     }
 
-    pub fn buffer_and_cursor_mut(&mut self) -> (&mut crate::core::buffer::RopeBuffer, &mut CursorPosition) {
-        (self.document.buffer_mut(), &mut self.cursor)
+    #[cfg(feature = "tree-sitter")]
+    pub fn syntax_tree(&self) -> Option<&SyntaxTree> {
+        self.syntax_tree.as_ref()
     }
 
-    pub fn buffer_cursor_and_viewport_mut(&mut self) -> (&mut crate::core::buffer::RopeBuffer, &mut CursorPosition, &mut Viewport) {
-        (self.document.buffer_mut(), &mut self.cursor, &mut self.viewport)
+    pub fn syntax_tokens(&self, line_idx: usize) -> Vec<Token> {
+        #[cfg(feature = "tree-sitter")]
+        if let Some(tree) = &self.syntax_tree {
+            return tree.line_tokens(self.document.buffer(), line_idx);
+        }
+        self.highlighter
+            .as_ref()
+            .map(|h| h.line_tokens(self.document.buffer(), line_idx))
+            .unwrap_or_default()
+    }
+
+    /// Applies pending edits to the highlighter and lexes up to the bottom
+    /// of the viewport. Called before each redraw.
+    pub fn update_highlights(&mut self) {
+        let edits = self.document.take_edits();
+        if self.highlighter.is_none() {
+            // An unnamed buffer may gain a `#!` line.
+            if !edits.is_empty() && self.document.file_path().is_none() {
+                self.detect_syntax();
+            }
+            if self.highlighter.is_none() {
+                return;
+            }
+        }
+        // Soft wrap can scroll past the viewport offset to reach the cursor.
+        let last = self.viewport.scroll_offset().max(self.cursor.line) + self.viewport.height();
+        if let Some(highlighter) = &mut self.highlighter {
+            for edit in &edits {
+                highlighter.edit(edit);
+            }
+            highlighter.update(self.document.buffer(), last);
+        }
+        #[cfg(feature = "tree-sitter")]
+        if let Some(tree) = &mut self.syntax_tree {
+            for edit in &edits {
+                tree.edit(edit);
+            }
+            tree.update(self.document.buffer());
+        }
+    }
+
+    pub fn auto_pairs(&self) -> AutoPairs {
+        let language = self.grammar().map_or("", |g| g.name);
+        self.config.editor.auto_pairs(language)
+    }
+
+    pub fn bracket_pairs(&self) -> &BracketPairs {
+        &self.bracket_pairs
+    }
+
+    pub fn bracket_match(&self) -> Option<(usize, usize)> {
+        let buffer = self.document.buffer();
+        let idx = buffer.line_to_char(self.cursor.line) + self.cursor.column;
+        BracketFeature::find_match(buffer, idx, &self.bracket_pairs, &mut self.code_filter())
+    }
+
+    pub fn enclosing_brackets(&self, range: Range<usize>) -> Option<(usize, usize)> {
+        let buffer = self.document.buffer();
+        BracketFeature::enclosing(buffer, range, &self.bracket_pairs, &mut self.code_filter())
+    }
+
+    /// Whether a char is code rather than string or comment text, going by
+    /// the current highlighting. Without it everything counts as code.
+    fn code_filter(&self) -> impl FnMut(usize) -> bool + '_ {
+        let buffer = self.document.buffer();
+        let mut line_tokens: Option<(usize, Vec<Token>)> = None;
+        move |idx| {
+            let line = buffer.char_to_line(idx);
+            if line_tokens.as_ref().is_none_or(|(cached, _)| *cached != line) {
+                line_tokens = Some((line, self.syntax_tokens(line)));
+            }
+            let column = idx - buffer.line_to_char(line);
+            let tokens = line_tokens.as_ref().map(|(_, tokens)| tokens.as_slice()).unwrap_or_default();
+            !tokens.iter().any(|token| {
+                token.range.contains(&column)
+                    && (token.scope.starts_with("string") || token.scope.starts_with("comment"))
+            })
+        }
+    }
+
+    pub fn selection(&self) -> Option<&Selection> {
+        self.selection.as_ref()
+    }
+
+    pub fn selection_range(&self) -> Range<usize> {
+        let buffer = self.document.buffer();
+        match &self.selection {
+            Some(selection) => selection.char_range(buffer),
+            None => {
+                let idx = buffer.line_to_char(self.cursor.line) + self.cursor.column;
+                idx..idx
+            }
+        }
+    }
+
+    pub fn set_selection(&mut self, selection: Option<Selection>) {
+        self.selection_history.clear();
+        self.select(selection);
+    }
+
+    pub fn expand_selection(&mut self, selection: Selection) {
+        let previous = self.selection.unwrap_or(Selection::new(self.cursor, self.cursor));
+        self.selection_history.push(previous);
+        self.select(Some(selection));
+    }
+
+    pub fn shrink_selection(&mut self) {
+        let previous = self.selection_history.pop().filter(|s| !s.is_empty());
+        match previous {
+            Some(selection) => self.select(Some(selection)),
+            None => {
+                self.selection_history.clear();
+                self.selection = None;
+            }
+        }
+    }
+
+    fn select(&mut self, selection: Option<Selection>) {
+        if let Some(selection) = &selection {
+            self.cursor = selection.head;
+            self.viewport.adjust_for_cursor(self.cursor.line);
+        }
+        self.selection = selection;
+    }
+
+    pub fn recovery(&self) -> Option<&SwapRecovery> {
+        self.recovery.as_ref()
+    }
+
+    pub fn recovery_mut(&mut self) -> Option<&mut SwapRecovery> {
+        self.recovery.as_mut()
+    }
+
+    pub fn take_recovery(&mut self) -> Option<SwapRecovery> {
+        self.recovery.take()
+    }
+
+    pub fn backup_policy(&self) -> BackupPolicy {
+        self.backup_policy
+    }
+
+    pub fn set_backup_policy(&mut self, policy: BackupPolicy) {
+        self.backup_policy = policy;
+    }
+
+    pub fn backups(&self) -> &[BackupEntry] {
+        &self.backups
+    }
+
+    pub fn set_backups(&mut self, backups: Vec<BackupEntry>) {
+        self.backups = backups;
+        self.selected_backup = 0;
+    }
+
+    pub fn selected_backup(&self) -> usize {
+        self.selected_backup
+    }
+
+    pub fn set_selected_backup(&mut self, index: usize) {
+        self.selected_backup = index.min(self.backups.len().saturating_sub(1));
+    }
+
+    pub fn auto_save(&self) -> &AutoSaveSettings {
+        &self.auto_save
+    }
+
+    pub fn set_auto_save(&mut self, settings: AutoSaveSettings) {
+        self.auto_save = settings;
+    }
+
+    pub fn tasks(&self) -> Option<&TaskSpawner> {
+        self.tasks.as_ref()
+    }
+
+    pub fn set_tasks(&mut self, tasks: TaskSpawner) {
+        self.tasks = Some(tasks);
+    }
+
+    pub fn confirmation(&self) -> Option<&Confirmation> {
+        self.confirmation.as_ref()
+    }
+
+    pub fn confirmation_mut(&mut self) -> Option<&mut Confirmation> {
+        self.confirmation.as_mut()
+    }
+
+    pub fn confirm(&mut self, confirmation: Confirmation) {
+        self.confirmation = Some(confirmation);
+        self.mode = EditorMode::Confirm;
+    }
+
+    pub fn set_after_save_as(&mut self, action: Option<PendingAction>) {
+        self.after_save_as = action;
+    }
+
+    pub fn take_after_save_as(&mut self) -> Option<PendingAction> {
+        self.after_save_as.take()
+    }
+
+    pub fn take_confirmation(&mut self) -> Option<Confirmation> {
+        if self.mode == EditorMode::Confirm {
+            self.mode = EditorMode::Normal;
+        }
+        self.confirmation.take()
+    }
+
+    pub fn path_candidates(&self) -> &[PathCandidate] {
+        &self.path_candidates
+    }
+
+    pub fn set_path_candidates(&mut self, candidates: Vec<PathCandidate>) {
+        self.path_candidates = candidates;
+        self.selected_candidate = None;
+    }
+
+    pub fn selected_candidate(&self) -> Option<usize> {
+        self.selected_candidate
+    }
+
+    pub fn set_selected_candidate(&mut self, index: Option<usize>) {
+        self.selected_candidate = index.filter(|i| *i < self.path_candidates.len());
+    }
+
+    pub fn file_picker(&self) -> &FilePicker {
+        &self.file_picker
+    }
+
+    pub fn file_picker_mut(&mut self) -> &mut FilePicker {
+        &mut self.file_picker
+    }
+
+    pub fn recent_files(&self) -> &RecentList {
+        &self.recent_files
+    }
+
+    pub fn recent_files_mut(&mut self) -> &mut RecentList {
+        &mut self.recent_files
+    }
+
+    pub fn set_recent_files(&mut self, list: RecentList) {
+        self.recent_files = list;
+    }
+
+    pub fn explorer(&self) -> &FileTree {
+        &self.explorer
+    }
+
+    pub fn explorer_mut(&mut self) -> &mut FileTree {
+        &mut self.explorer
+    }
+
+    pub fn is_explorer_visible(&self) -> bool {
+        self.explorer_visible
+    }
+
+    pub fn set_explorer_visible(&mut self, visible: bool) {
+        self.explorer_visible = visible;
+        if !visible && self.mode == EditorMode::Explorer {
+            self.mode = EditorMode::Normal;
+        }
+    }
+
+    pub fn explorer_prompt(&self) -> Option<&ExplorerPrompt> {
+        self.explorer_prompt.as_ref()
+    }
+
+    pub fn set_explorer_prompt(&mut self, prompt: Option<ExplorerPrompt>) {
+        self.explorer_prompt = prompt;
+    }
+
+    pub fn arg_list(&self) -> &ArgList {
+        &self.arg_list
+    }
+
+    pub fn arg_list_mut(&mut self) -> &mut ArgList {
+        &mut self.arg_list
+    }
+
+    pub fn set_arg_list(&mut self, arg_list: ArgList) {
+        self.arg_list = arg_list;
+    }
+
+    pub fn pager(&self) -> Option<&PagerState> {
+        self.pager.as_ref()
+    }
+
+    pub fn pager_mut(&mut self) -> Option<&mut PagerState> {
+        self.pager.as_mut()
+    }
+
+    pub fn set_pager(&mut self, pager: Option<PagerState>) {
+        self.pager = pager;
+    }
+
+    pub fn last_search(&self) -> Option<&str> {
+        self.last_search.as_deref()
+    }
+
+    pub fn set_last_search(&mut self, query: String) {
+        self.last_search = Some(query);
+    }
+
+    pub fn large_files(&self) -> &LargeFileSettings {
+        &self.large_files
+    }
+
+    pub fn set_large_files(&mut self, settings: LargeFileSettings) {
+        self.large_files = settings;
+    }
+
+    pub fn loading(&self) -> Option<&LoadProgress> {
+        self.loading.as_ref()
+    }
+
+    pub fn loading_mut(&mut self) -> Option<&mut LoadProgress> {
+        self.loading.as_mut()
+    }
+
+    pub fn set_loading(&mut self, progress: Option<LoadProgress>) {
+        self.loading = progress;
+    }
+
+    pub fn take_loading(&mut self) -> Option<LoadProgress> {
+        self.loading.take()
+    }
+
+    pub fn check_recovery(&mut self) {
+        if self.loading.is_some() {
+            return;
+        }
+        self.recovery = RecoverFile::check(&self.document);
+        if let Some(recovery) = &self.recovery {
+            self.mode = EditorMode::Recovery;
+            self.message.set(format!(
+                "Swap file found for {}",
+                recovery.file_path.display()
+            ));
+        }
+    }
+
+    pub fn refuse_read_only(&mut self) -> bool {
+        if !self.document.is_read_only() {
+            return false;
+        }
+        let hint = if self.document.buffer().is_mapped() {
+            "memory-mapped"
+        } else {
+            "Toggle Read-Only to edit"
+        };
+        self.message.set(format!(
+            "{} is read-only ({})",
+            self.document.file_name(),
+            hint
+        ));
+        true
+    }
+
+    /// `None` for read-only documents, after telling the user.
+    pub fn buffer_and_cursor_mut(&mut self) -> Option<(&mut crate::core::buffer::RopeBuffer, &mut CursorPosition)> {
+        if self.refuse_read_only() {
+            return None;
+        }
+        Some((self.document.buffer_mut().ok()?, &mut self.cursor))
+    }
+
+    pub fn buffer_cursor_and_viewport_mut(&mut self) -> Option<(&mut crate::core::buffer::RopeBuffer, &mut CursorPosition, &mut Viewport)> {
+        if self.refuse_read_only() {
+            return None;
+        }
+        Some((self.document.buffer_mut().ok()?, &mut self.cursor, &mut self.viewport))
     }
 
     pub fn cursor_and_viewport_mut(&mut self) -> (&mut CursorPosition, &mut Viewport) {
@@ -1,16 +1,23 @@
+use std::time::Instant;
+
 pub struct MessageState {
     message: Option<String>,
+    set_at: Option<Instant>,
 }
 
 impl MessageState {
     pub fn new(initial_message: &str) -> Self {
         Self {
             message: Some(initial_message.to_string()),
+            set_at: Some(Instant::now()),
         }
     }
 
     pub fn empty() -> Self {
-        Self { message: None }
+        Self {
+            message: None,
+            set_at: None,
+        }
     }
 
     pub fn get(&self) -> Option<&str> {
@@ -19,10 +26,16 @@ impl MessageState {
 
     pub fn set(&mut self, msg: String) {
         self.message = Some(msg);
+        self.set_at = Some(Instant::now());
     }
 
     pub fn clear(&mut self) {
         self.message = None;
+        self.set_at = None;
+    }
+
+    pub fn set_at(&self) -> Option<Instant> {
+        self.set_at
     }
 
     pub fn has_message(&self) -> bool {
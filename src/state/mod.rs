@@ -1,7 +1,9 @@
+mod confirmation;
 mod editor_state;
 mod mode;
 mod message;
 
+pub use confirmation::{ConfirmChoice, Confirmation, ConfirmationDialog, PendingAction};
 pub use editor_state::EditorState;
 pub use mode::EditorMode;
 pub use message::MessageState;
\ No newline at end of file
@@ -4,13 +4,34 @@ pub enum EditorMode {
     CommandPalette,
     OpenFile,
     SaveAs,
+    Recovery,
+    Backups,
+    Confirm,
+    FindFile,
+    RecentFiles,
+    SelectTheme,
+    Explorer,
+    ExplorerInput,
+    Search,
+    GotoLine,
 }
 
 impl EditorMode {
     pub fn is_dialog(&self) -> bool {
         matches!(
             self,
-            EditorMode::CommandPalette | EditorMode::OpenFile | EditorMode::SaveAs
+            EditorMode::CommandPalette
+                | EditorMode::OpenFile
+                | EditorMode::SaveAs
+                | EditorMode::Recovery
+                | EditorMode::Backups
+                | EditorMode::Confirm
+                | EditorMode::FindFile
+                | EditorMode::RecentFiles
+                | EditorMode::SelectTheme
+                | EditorMode::ExplorerInput
+                | EditorMode::Search
+                | EditorMode::GotoLine
         )
     }
 
@@ -19,7 +40,16 @@ impl EditorMode {
             EditorMode::CommandPalette => "Command Palette",
             EditorMode::OpenFile => "Open File",
             EditorMode::SaveAs => "Save As",
-            EditorMode::Normal => "",
+            EditorMode::Recovery => "Swap File Found",
+            EditorMode::Backups => "Backups",
+            EditorMode::Confirm => "Confirm",
+            EditorMode::FindFile => "Find File",
+            EditorMode::RecentFiles => "Open Recent",
+            EditorMode::SelectTheme => "Select Theme",
+            EditorMode::ExplorerInput => "File Explorer",
+            EditorMode::Search => "Search",
+            EditorMode::GotoLine => "Go to Line",
+            EditorMode::Normal | EditorMode::Explorer => "",
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,74 @@
+use crate::state::EditorState;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::time::SystemTime;
+
+pub fn render_backup_list(f: &mut Frame, state: &EditorState) {
+    let theme = state.theme();
+    let area = f.area();
+    let popup_width = area.width.min(80);
+    let popup_height = (state.backups().len() as u16 + 2).clamp(3, 12).min(area.height);
+
+    let popup_area = Rect {
+        x: (area.width - popup_width) / 2,
+        y: (area.height - popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(state.mode().dialog_title())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_border))
+        .style(Style::default().bg(theme.dialog_bg));
+
+    let inner_area = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let visible = inner_area.height as usize;
+    let offset = state.selected_backup().saturating_sub(visible.saturating_sub(1));
+
+    let lines: Vec<Line> = state
+        .backups()
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, entry)| {
+            let style = if i == state.selected_backup() {
+                Style::default()
+                    .fg(theme.dialog_highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.dialog_fg)
+            };
+            let age = entry.modified.map(format_age).unwrap_or_default();
+            Line::from(Span::styled(
+                format!("  {:<10} {}", age, entry.path.display()),
+                style,
+            ))
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), inner_area);
+}
+
+fn format_age(time: SystemTime) -> String {
+    let secs = SystemTime::now()
+        .duration_since(time)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match secs {
+        0..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
@@ -1,6 +1,4 @@
 use crate::state::EditorState;
-use crate::ui::theme::Theme;
-use ratatui::layout::Position;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
@@ -10,7 +8,7 @@ use ratatui::{
 };
 
 pub fn render_command_palette(f: &mut Frame, state: &EditorState) {
-    let theme = Theme::default();
+    let theme = state.theme();
     let area = f.area();
     let popup_width = area.width.min(60);
     let popup_height = 12;
@@ -27,6 +25,7 @@ pub fn render_command_palette(f: &mut Frame, state: &EditorState) {
     let block = Block::default()
         .title("Command Palette")
         .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_border))
         .style(Style::default().bg(theme.dialog_bg));
 
     let inner_area = block.inner(popup_area);
@@ -37,9 +36,9 @@ pub fn render_command_palette(f: &mut Frame, state: &EditorState) {
         .constraints([Constraint::Length(1), Constraint::Min(1)])
         .split(inner_area);
 
-    let input = Paragraph::new(format!("> {}", state.command_input()))
-        .style(Style::default().fg(theme.dialog_fg));
-    f.render_widget(input, chunks[0]);
+    state
+        .command_field()
+        .render(f, chunks[0], "> ", Style::default().fg(theme.dialog_fg));
 
     let commands: Vec<Line> = state
         .filtered_commands()
@@ -60,8 +59,4 @@ pub fn render_command_palette(f: &mut Frame, state: &EditorState) {
 
     let list = Paragraph::new(commands);
     f.render_widget(list, chunks[1]);
-
-    let cursor_x = popup_area.x + 3 + state.command_input().len() as u16;
-    let cursor_y = popup_area.y + 1;
-    f.set_cursor_position(Position::new(cursor_x, cursor_y));
 }
\ No newline at end of file
@@ -1,4 +1,93 @@
-pub struct ConfirmationDialog;
+use crate::state::ConfirmationDialog;
+use crate::ui::theme::Theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
 
-impl ConfirmationDialog {
-}
\ No newline at end of file
+const DIALOG_WIDTH: u16 = 60;
+const DIALOG_HEIGHT: u16 = 7;
+const BUTTON_GAP: u16 = 2;
+
+pub fn render_confirmation(f: &mut Frame, dialog: &ConfirmationDialog, theme: &Theme) {
+    let popup_area = area(f.area());
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(dialog.title())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_border))
+        .style(Style::default().bg(theme.dialog_bg));
+
+    let inner_area = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let message_area = Rect {
+        height: inner_area.height.saturating_sub(2),
+        ..inner_area
+    };
+    let message = Paragraph::new(dialog.message())
+        .style(Style::default().fg(theme.dialog_fg))
+        .wrap(Wrap { trim: true });
+    f.render_widget(message, message_area);
+
+    for (choice, area) in dialog.buttons().iter().zip(button_areas(dialog, popup_area)) {
+        let style = if *choice == dialog.selected() {
+            Style::default()
+                .fg(theme.dialog_highlight)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(theme.dialog_fg)
+        };
+        let button = Paragraph::new(Line::from(Span::styled(
+            format!("[ {} ]", choice.label()),
+            style,
+        )));
+        f.render_widget(button, area);
+    }
+}
+
+pub fn confirmation_button_at(
+    dialog: &ConfirmationDialog,
+    screen: Rect,
+    column: u16,
+    row: u16,
+) -> Option<usize> {
+    button_areas(dialog, area(screen))
+        .into_iter()
+        .position(|r| row == r.y && column >= r.x && column < r.x + r.width)
+}
+
+fn area(screen: Rect) -> Rect {
+    let width = screen.width.min(DIALOG_WIDTH);
+    let height = screen.height.min(DIALOG_HEIGHT);
+    Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+fn button_areas(dialog: &ConfirmationDialog, popup_area: Rect) -> Vec<Rect> {
+    let widths: Vec<u16> = dialog
+        .buttons()
+        .iter()
+        .map(|b| b.label().len() as u16 + 4)
+        .collect();
+    let total = widths.iter().sum::<u16>() + BUTTON_GAP * (widths.len() as u16).saturating_sub(1);
+    let mut x = popup_area.x + popup_area.width.saturating_sub(total) / 2;
+    let y = popup_area.y + popup_area.height.saturating_sub(2);
+    widths
+        .into_iter()
+        .map(|width| {
+            let area = Rect { x, y, width, height: 1 };
+            x += width + BUTTON_GAP;
+            area
+        })
+        .collect()
+}
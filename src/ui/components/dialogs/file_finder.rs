@@ -0,0 +1,94 @@
+use crate::state::EditorState;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+pub fn render_file_finder(f: &mut Frame, state: &EditorState) {
+    let theme = state.theme();
+    let area = f.area();
+    let popup_width = (area.width * 9 / 10).max(area.width.min(40));
+    let popup_height = (area.height * 8 / 10).max(area.height.min(8));
+
+    let popup_area = Rect {
+        x: (area.width - popup_width) / 2,
+        y: (area.height - popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    f.render_widget(Clear, popup_area);
+
+    let picker = state.file_picker();
+    let status = if picker.is_indexing() {
+        format!(" {} / {} (indexing…) ", picker.matched_count(), picker.total())
+    } else {
+        format!(" {} / {} ", picker.matched_count(), picker.total())
+    };
+    let block = Block::default()
+        .title(state.mode().dialog_title())
+        .title_bottom(Line::from(status).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_border))
+        .style(Style::default().bg(theme.dialog_bg));
+
+    let inner_area = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner_area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(rows[1]);
+
+    let visible = columns[0].height as usize;
+    let offset = picker.selected().saturating_sub(visible.saturating_sub(1));
+    let list: Vec<Line> = picker
+        .results()
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, result)| {
+            let selected = i == picker.selected();
+            let base = if selected {
+                Style::default()
+                    .fg(theme.dialog_highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.dialog_fg)
+            };
+            let matched = base.fg(theme.dialog_highlight).add_modifier(Modifier::UNDERLINED);
+            let mut spans = vec![Span::styled(if selected { "> " } else { "  " }, base)];
+            spans.extend(picker.path(result).chars().enumerate().map(|(ci, ch)| {
+                let style = if result.positions.contains(&ci) { matched } else { base };
+                Span::styled(ch.to_string(), style)
+            }));
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(list), columns[0]);
+
+    let preview_block = Block::default()
+        .borders(Borders::LEFT)
+        .style(Style::default().fg(theme.line_number));
+    let preview_area = preview_block.inner(columns[1]);
+    f.render_widget(preview_block, columns[1]);
+    let preview: Vec<Line> = picker
+        .preview()
+        .iter()
+        .take(preview_area.height as usize)
+        .map(|line| Line::from(Span::styled(line.replace('\t', "    "), Style::default().fg(theme.dialog_fg))))
+        .collect();
+    f.render_widget(Paragraph::new(preview), preview_area);
+
+    state
+        .command_field()
+        .render(f, rows[0], "> ", Style::default().fg(theme.dialog_fg));
+}
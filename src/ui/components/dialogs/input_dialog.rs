@@ -1,18 +1,21 @@
-use crate::state::EditorState;
-use crate::ui::theme::Theme;
-use ratatui::layout::Position;
+use crate::state::{EditorMode, EditorState};
 use ratatui::{
     Frame,
-    layout::Rect,
-    style::Style,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+const MAX_VISIBLE_CANDIDATES: usize = 10;
+
 pub fn render_input_dialog(f: &mut Frame, state: &EditorState) {
-    let theme = Theme::default();
+    let theme = state.theme();
     let area = f.area();
+    let candidates = state.path_candidates();
+    let list_height = candidates.len().min(MAX_VISIBLE_CANDIDATES) as u16;
     let popup_width = area.width.min(60);
-    let popup_height = 3;
+    let popup_height = (3 + list_height).min(area.height);
 
     let popup_area = Rect {
         x: (area.width - popup_width) / 2,
@@ -23,20 +26,51 @@ pub fn render_input_dialog(f: &mut Frame, state: &EditorState) {
 
     f.render_widget(Clear, popup_area);
 
-    let title = state.mode().dialog_title();
+    let title = state
+        .explorer_prompt()
+        .filter(|_| *state.mode() == EditorMode::ExplorerInput)
+        .map_or(state.mode().dialog_title(), |p| p.title());
 
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_border))
         .style(Style::default().bg(theme.dialog_bg));
 
     let inner_area = block.inner(popup_area);
     f.render_widget(block, popup_area);
 
-    let input = Paragraph::new(state.command_input()).style(Style::default().fg(theme.dialog_fg));
-    f.render_widget(input, inner_area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner_area);
+
+    let visible = chunks[1].height as usize;
+    let offset = state
+        .selected_candidate()
+        .map_or(0, |i| i.saturating_sub(visible.saturating_sub(1)));
+    let lines: Vec<Line> = candidates
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, candidate)| {
+            let style = if Some(i) == state.selected_candidate() {
+                Style::default()
+                    .fg(theme.dialog_highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else if candidate.is_dir {
+                Style::default().fg(theme.dialog_highlight)
+            } else {
+                Style::default().fg(theme.dialog_fg)
+            };
+            let suffix = if candidate.is_dir { "/" } else { "" };
+            Line::from(Span::styled(format!("  {}{}", candidate.name, suffix), style))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[1]);
 
-    let cursor_x = popup_area.x + 1 + state.command_input().len() as u16;
-    let cursor_y = popup_area.y + 1;
-    f.set_cursor_position(Position::new(cursor_x, cursor_y));
+    state
+        .command_field()
+        .render(f, chunks[0], "", Style::default().fg(theme.dialog_fg));
 }
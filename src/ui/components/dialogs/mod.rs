@@ -1,7 +1,17 @@
+mod backup_list;
 mod command_palette;
 mod input_dialog;
 mod confirmation;
+mod file_finder;
+mod recent_files;
+mod recovery;
+mod theme_picker;
 
+pub use backup_list::render_backup_list;
 pub use command_palette::render_command_palette;
+pub use file_finder::render_file_finder;
 pub use input_dialog::render_input_dialog;
-pub use confirmation::ConfirmationDialog;
\ No newline at end of file
+pub use recent_files::render_recent_files;
+pub use confirmation::{confirmation_button_at, render_confirmation};
+pub use recovery::render_recovery_dialog;
+pub use theme_picker::render_theme_picker;
\ No newline at end of file
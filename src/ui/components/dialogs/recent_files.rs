@@ -0,0 +1,73 @@
+use crate::state::EditorState;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+pub fn render_recent_files(f: &mut Frame, state: &EditorState) {
+    let theme = state.theme();
+    let area = f.area();
+    let list = state.recent_files();
+    let popup_width = area.width.min(80);
+    let popup_height = (list.total() as u16 + 3).clamp(4, 16).min(area.height);
+
+    let popup_area = Rect {
+        x: (area.width - popup_width) / 2,
+        y: (area.height - popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    f.render_widget(Clear, popup_area);
+
+    let status = format!(" {} / {} ", list.results().len(), list.total());
+    let block = Block::default()
+        .title(state.mode().dialog_title())
+        .title_bottom(Line::from(status).right_aligned())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_border))
+        .style(Style::default().bg(theme.dialog_bg));
+
+    let inner_area = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner_area);
+
+    let visible = rows[1].height as usize;
+    let offset = list.selected().saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = list
+        .results()
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, result)| {
+            let selected = i == list.selected();
+            let base = if selected {
+                Style::default()
+                    .fg(theme.dialog_highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.dialog_fg)
+            };
+            let matched = base.fg(theme.dialog_highlight).add_modifier(Modifier::UNDERLINED);
+            let mut spans = vec![Span::styled(if selected { "> " } else { "  " }, base)];
+            spans.extend(list.label(result).chars().enumerate().map(|(ci, ch)| {
+                let style = if result.positions.contains(&ci) { matched } else { base };
+                Span::styled(ch.to_string(), style)
+            }));
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), rows[1]);
+
+    state
+        .command_field()
+        .render(f, rows[0], "> ", Style::default().fg(theme.dialog_fg));
+}
@@ -0,0 +1,91 @@
+use crate::features::file_operations::DiffLine;
+use crate::state::EditorState;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+pub fn render_recovery_dialog(f: &mut Frame, state: &EditorState) {
+    let Some(recovery) = state.recovery() else {
+        return;
+    };
+    let theme = state.theme();
+    let area = f.area();
+    let popup_width = area.width.min(80);
+    let popup_height = if recovery.show_diff {
+        area.height.saturating_sub(4).max(7)
+    } else {
+        7
+    }
+    .min(area.height);
+
+    let popup_area = Rect {
+        x: (area.width - popup_width) / 2,
+        y: (area.height - popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(state.mode().dialog_title())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_border))
+        .style(Style::default().bg(theme.dialog_bg));
+
+    let inner_area = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(0)])
+        .split(inner_area);
+
+    let owner = if recovery.pid != 0 && recovery.owner_running {
+        format!("Written by process {}, which is still running.", recovery.pid)
+    } else if recovery.pid != 0 {
+        format!("Written by process {}.", recovery.pid)
+    } else {
+        String::new()
+    };
+    let header = vec![
+        Line::from(Span::styled(
+            format!("{} has unsaved changes from an earlier session.", recovery.file_path.display()),
+            Style::default().fg(theme.dialog_fg),
+        )),
+        Line::from(Span::styled(owner, Style::default().fg(theme.dialog_fg))),
+        Line::from(""),
+        Line::from(Span::styled(
+            "(R)ecover  (D)iff  (X) Discard swap  (Esc) Ignore",
+            Style::default()
+                .fg(theme.dialog_highlight)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ];
+    f.render_widget(Paragraph::new(header), chunks[0]);
+
+    if recovery.show_diff {
+        let diff: Vec<Line> = recovery
+            .diff
+            .iter()
+            .skip(recovery.scroll)
+            .take(chunks[1].height as usize)
+            .map(|line| match line {
+                DiffLine::Same(text) => {
+                    Line::from(Span::styled(format!("  {}", text), Style::default().fg(theme.dialog_fg)))
+                }
+                DiffLine::Added(text) => {
+                    Line::from(Span::styled(format!("+ {}", text), Style::default().fg(theme.diff_added)))
+                }
+                DiffLine::Removed(text) => {
+                    Line::from(Span::styled(format!("- {}", text), Style::default().fg(theme.diff_removed)))
+                }
+            })
+            .collect();
+        f.render_widget(Paragraph::new(diff), chunks[1]);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::state::EditorState;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+pub fn render_theme_picker(f: &mut Frame, state: &EditorState) {
+    let Some(picker) = state.theme_picker() else {
+        return;
+    };
+    let theme = state.theme();
+    let area = f.area();
+    let popup_width = area.width.min(40);
+    let popup_height = (picker.names().len() as u16 + 2).clamp(3, 14).min(area.height);
+
+    let popup_area = Rect {
+        x: (area.width - popup_width) / 2,
+        y: (area.height - popup_height) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(state.mode().dialog_title())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.dialog_border))
+        .style(Style::default().bg(theme.dialog_bg));
+
+    let inner_area = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let visible = inner_area.height as usize;
+    let offset = picker.selected().saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = picker
+        .names()
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, name)| {
+            let selected = i == picker.selected();
+            let style = if selected {
+                Style::default()
+                    .fg(theme.dialog_highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.dialog_fg)
+            };
+            let marker = if selected { "> " } else { "  " };
+            Line::from(Span::styled(format!("{}{}", marker, name), style))
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), inner_area);
+}
@@ -1,46 +1,255 @@
+use crate::core::buffer::RopeBuffer;
+use crate::features::navigation::SearchFeature;
 use crate::state::EditorState;
 use crate::ui::components::line_numbers::LineNumbers;
-use crate::ui::theme::Theme;
 use ratatui::{
     layout::{Position, Rect},
-    style::{Color, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use std::borrow::Cow;
+use std::ops::Range;
+use unicode_width::UnicodeWidthChar;
+
+struct LineLayout {
+    rows: Vec<Vec<Cell>>,
+    cursor: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    ch: char,
+    /// Char index in the buffer line; the spaces of an expanded tab share it.
+    index: usize,
+    overflow: bool,
+}
 
 pub fn render_editor(f: &mut Frame, state: &EditorState, area: Rect) {
-    let theme = Theme::default();
+    let theme = state.theme();
+    let editor = &state.config().editor;
+    let indentation = state.indentation();
+    let max_line_length = state.document().settings().max_line_length;
     let buffer = state.document().buffer();
-    let scroll = state.viewport().scroll_offset();
     let cursor = state.cursor();
+    let search = state.last_search().filter(|q| !q.is_empty());
+    let brackets: Vec<usize> = state.bracket_match().into_iter().flat_map(|(a, b)| [a, b]).collect();
 
     let viewport_height = area.height as usize;
+    let gutter = if editor.line_numbers { LineNumbers::width() } else { 0 };
+    let text_width = (area.width as usize).saturating_sub(gutter).max(1);
+    let layout = |line_idx: usize, max_rows: usize| {
+        let cursor_column = (line_idx == cursor.line).then_some(cursor.column);
+        let options = LayoutOptions {
+            tab_width: indentation.tab_width,
+            width: text_width,
+            wrap: editor.wrap,
+            limit: max_line_length,
+        };
+        layout_line(buffer, line_idx, cursor_column, &options, max_rows)
+    };
+
+    // Wrapped lines can push the cursor below the area, so start late enough
+    // to keep it on screen.
+    let mut first = state.viewport().scroll_offset();
+    if editor.wrap && cursor.line >= first {
+        let mut rows: usize = (first..=cursor.line)
+            .map(|i| layout(i, viewport_height).rows.len())
+            .sum();
+        while rows > viewport_height && first < cursor.line {
+            rows -= layout(first, viewport_height).rows.len();
+            first += 1;
+        }
+    }
+
     let mut lines = Vec::new();
-    let end_line = (scroll + viewport_height).min(buffer.len_lines());
+    let mut cursor_screen = None;
+    let mut line_idx = first;
+    while lines.len() < viewport_height && line_idx < buffer.len_lines() {
+        let line = layout(line_idx, viewport_height - lines.len());
+        if let Some((row, column)) = line.cursor {
+            cursor_screen = Some((lines.len() + row, column));
+        }
 
-    for line_idx in scroll..end_line {
-        if let Some(line_content) = buffer.line(line_idx) {
-            let line_num = LineNumbers::format(line_idx);
-            let line_text = line_content.trim_end_matches(&['\n', '\r'][..]).to_string();
+        let is_cursor_line = line_idx == cursor.line;
+        let base = if is_cursor_line {
+            Style::default().bg(theme.cursor_line)
+        } else {
+            Style::default()
+        };
+        // Later highlights win where they overlap.
+        let mut highlights: Vec<(Range<usize>, Style)> = Vec::new();
+        highlights.extend(
+            state
+                .syntax_tokens(line_idx)
+                .into_iter()
+                .map(|token| (token.range, theme.syntax_style(token.scope))),
+        );
+        let line_start = buffer.line_to_char(line_idx);
+        let bracket_style = Style::default()
+            .bg(theme.bracket_match)
+            .add_modifier(Modifier::BOLD);
+        highlights.extend(
+            brackets
+                .iter()
+                .filter(|&&idx| buffer.char_to_line(idx) == line_idx)
+                .map(|&idx| (idx - line_start..idx - line_start + 1, bracket_style)),
+        );
+        if let Some(query) = search
+            && let Some(text) = buffer.line(line_idx)
+        {
+            let match_style = Style::default().bg(theme.search_match);
+            highlights.extend(
+                SearchFeature::line_matches(&Cow::from(text), query)
+                    .into_iter()
+                    .map(|range| (range, match_style)),
+            );
+        }
+        if let Some(columns) = state.selection().and_then(|s| s.columns_on(line_idx)) {
+            highlights.push((columns, Style::default().bg(theme.selection)));
+        }
+        let style_of = |cell: &Cell| {
+            let mut style = base;
+            if cell.overflow {
+                style = style.fg(theme.line_overflow);
+            }
+            for (range, highlight) in &highlights {
+                if range.contains(&cell.index) {
+                    style = style.patch(*highlight);
+                }
+            }
+            style
+        };
 
-            let line_num_span = Span::styled(line_num, Style::default().fg(theme.line_number));
-            let text_span = Span::raw(line_text);
-            lines.push(Line::from(vec![line_num_span, text_span]));
+        for (row, cells) in line.rows.into_iter().enumerate() {
+            let mut spans = Vec::new();
+            if editor.line_numbers {
+                let (number, color) = match (row, is_cursor_line) {
+                    (0, true) => (LineNumbers::format(line_idx), theme.line_number_current),
+                    (0, false) => (LineNumbers::format(line_idx), theme.line_number),
+                    _ => (" ".repeat(gutter), theme.line_number),
+                };
+                spans.push(Span::styled(number, Style::default().fg(color)));
+            }
+            let mut row_width = 0;
+            let mut run = String::new();
+            let mut run_style = base;
+            for cell in &cells {
+                let style = style_of(cell);
+                if style != run_style && !run.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut run), run_style));
+                }
+                run_style = style;
+                run.push(cell.ch);
+                row_width += cell.ch.width().unwrap_or(0);
+            }
+            if !run.is_empty() {
+                spans.push(Span::styled(run, run_style));
+            }
+            if is_cursor_line && row_width < text_width {
+                spans.push(Span::styled(" ".repeat(text_width - row_width), base));
+            }
+            lines.push(Line::from(spans));
         }
+        line_idx += 1;
     }
 
-    let paragraph = Paragraph::new(lines).block(Block::default().borders(Borders::NONE));
+    let paragraph = Paragraph::new(lines)
+        .style(Style::default().fg(theme.foreground).bg(theme.background))
+        .block(Block::default().borders(Borders::NONE));
 
     f.render_widget(paragraph, area);
 
-    if cursor.line >= scroll && cursor.line < scroll + viewport_height {
-        let cursor_y = area.y + (cursor.line - scroll) as u16;
-        let line_num_width = 5;
-        let cursor_x = area.x + line_num_width + cursor.column as u16;
-
-        if cursor_x < area.x + area.width && cursor_y < area.y + area.height {
+    if let Some((row, column)) = cursor_screen
+        && row < viewport_height
+    {
+        let cursor_x = area.x + (gutter + column) as u16;
+        let cursor_y = area.y + row as u16;
+        if cursor_x < area.x + area.width {
             f.set_cursor_position(Position::new(cursor_x, cursor_y));
         }
     }
-}
\ No newline at end of file
+}
+
+struct LayoutOptions {
+    tab_width: usize,
+    width: usize,
+    wrap: bool,
+    limit: Option<usize>,
+}
+
+/// Lays out at most `max_rows` rows of a line. Without wrapping the line is
+/// cut at `width`, so huge lines are never walked past the visible part.
+fn layout_line(
+    buffer: &RopeBuffer,
+    line_idx: usize,
+    cursor_column: Option<usize>,
+    options: &LayoutOptions,
+    max_rows: usize,
+) -> LineLayout {
+    let LayoutOptions {
+        tab_width,
+        width,
+        wrap,
+        limit,
+    } = *options;
+    let mut layout = LineLayout {
+        rows: vec![Vec::new()],
+        cursor: None,
+    };
+    let Some(line) = buffer.line(line_idx) else {
+        return layout;
+    };
+    let len = buffer.line_len(line_idx);
+    let mut row_width = 0;
+    let mut display_column = 0;
+    let mut complete = true;
+
+    for (i, ch) in line.chars().take(len).enumerate() {
+        let char_width = match ch {
+            '\t' => tab_width - display_column % tab_width,
+            _ => ch.width().unwrap_or(0),
+        };
+        if row_width + char_width > width {
+            if !wrap || layout.rows.len() >= max_rows {
+                complete = false;
+                break;
+            }
+            layout.rows.push(Vec::new());
+            row_width = 0;
+        }
+        if cursor_column == Some(i) {
+            layout.cursor = Some((layout.rows.len() - 1, row_width));
+        }
+        let row = layout.rows.last_mut().expect("layout has a row");
+        let overflow = limit.is_some_and(|limit| display_column >= limit);
+        match ch {
+            '\t' => row.extend(std::iter::repeat_n(
+                Cell {
+                    ch: ' ',
+                    index: i,
+                    overflow,
+                },
+                char_width,
+            )),
+            _ => row.push(Cell {
+                ch,
+                index: i,
+                overflow,
+            }),
+        }
+        row_width += char_width;
+        display_column += char_width;
+    }
+
+    if complete && layout.cursor.is_none() && cursor_column.is_some() {
+        // A cursor past the end of a full wrapped row starts the next one.
+        if wrap && row_width >= width && layout.rows.len() < max_rows {
+            layout.rows.push(Vec::new());
+            row_width = 0;
+        }
+        layout.cursor = Some((layout.rows.len() - 1, row_width));
+    }
+    layout
+}
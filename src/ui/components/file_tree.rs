@@ -0,0 +1,63 @@
+use crate::state::{EditorMode, EditorState};
+use crate::utils::paths;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+
+pub fn render_file_tree(f: &mut Frame, state: &EditorState, area: Rect) {
+    let theme = state.theme();
+    let tree = state.explorer();
+    let focused = *state.mode() == EditorMode::Explorer;
+    let current = state.document().file_path().map(|p| paths::absolute(p));
+
+    let title = tree
+        .root()
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| tree.root().display().to_string());
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::RIGHT)
+        .border_style(Style::default().fg(theme.line_number));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let visible = inner.height as usize;
+    let offset = tree.selected().saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = tree
+        .entries()
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, entry)| {
+            let marker = match (entry.is_dir, entry.expanded) {
+                (true, true) => "▾ ",
+                (true, false) => "▸ ",
+                _ => "  ",
+            };
+            let mut style = if current.as_ref() == Some(&entry.path) {
+                Style::default()
+                    .fg(theme.tree_current_file)
+                    .add_modifier(Modifier::BOLD)
+            } else if entry.is_dir {
+                Style::default().fg(theme.tree_directory)
+            } else {
+                Style::default()
+            };
+            if i == tree.selected() && focused {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            let indent = "  ".repeat(entry.depth);
+            Line::from(Span::styled(
+                format!("{}{}{}", indent, marker, entry.name),
+                style,
+            ))
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
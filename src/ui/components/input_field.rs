@@ -0,0 +1,258 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Position, Rect},
+    style::Style,
+    widgets::Paragraph,
+};
+use unicode_width::UnicodeWidthChar;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldEdit {
+    Changed,
+    CursorMoved,
+    Unhandled,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputField {
+    text: String,
+    cursor: usize,
+}
+
+impl InputField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn set_text(&mut self, text: String) {
+        self.cursor = text.chars().count();
+        self.text = text;
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn insert_char(&mut self, ch: char) {
+        let idx = self.byte_index(self.cursor);
+        self.text.insert(idx, ch);
+        self.cursor += 1;
+    }
+
+    pub fn insert_str(&mut self, text: &str) {
+        for ch in text.chars().filter(|c| !c.is_control()) {
+            self.insert_char(ch);
+        }
+    }
+
+    pub fn backspace(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.delete_range(self.cursor - 1, self.cursor);
+        true
+    }
+
+    pub fn delete(&mut self) -> bool {
+        if self.cursor >= self.len() {
+            return false;
+        }
+        self.delete_range(self.cursor, self.cursor + 1);
+        true
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.len());
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor = self.len();
+    }
+
+    pub fn move_word_left(&mut self) {
+        self.cursor = self.word_start_before(self.cursor);
+    }
+
+    pub fn move_word_right(&mut self) {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut idx = self.cursor;
+        while idx < chars.len() && !is_word_char(chars[idx]) {
+            idx += 1;
+        }
+        while idx < chars.len() && is_word_char(chars[idx]) {
+            idx += 1;
+        }
+        self.cursor = idx;
+    }
+
+    pub fn delete_word_before(&mut self) -> bool {
+        let start = self.word_start_before(self.cursor);
+        if start == self.cursor {
+            return false;
+        }
+        self.delete_range(start, self.cursor);
+        true
+    }
+
+    pub fn delete_to_start(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.delete_range(0, self.cursor);
+        true
+    }
+
+    pub fn delete_to_end(&mut self) -> bool {
+        if self.cursor >= self.len() {
+            return false;
+        }
+        self.delete_range(self.cursor, self.len());
+        true
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> FieldEdit {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        let changed = |did: bool| {
+            if did {
+                FieldEdit::Changed
+            } else {
+                FieldEdit::CursorMoved
+            }
+        };
+
+        match key.code {
+            KeyCode::Char('w') if ctrl => changed(self.delete_word_before()),
+            KeyCode::Char('u') if ctrl => changed(self.delete_to_start()),
+            KeyCode::Char('k') if ctrl => changed(self.delete_to_end()),
+            KeyCode::Char('a') if ctrl => {
+                self.move_home();
+                FieldEdit::CursorMoved
+            }
+            KeyCode::Char('e') if ctrl => {
+                self.move_end();
+                FieldEdit::CursorMoved
+            }
+            KeyCode::Char(_) if ctrl || alt => FieldEdit::Unhandled,
+            KeyCode::Char(c) => {
+                self.insert_char(c);
+                FieldEdit::Changed
+            }
+            KeyCode::Backspace if ctrl || alt => changed(self.delete_word_before()),
+            KeyCode::Backspace => changed(self.backspace()),
+            KeyCode::Delete => changed(self.delete()),
+            KeyCode::Left if ctrl || alt => {
+                self.move_word_left();
+                FieldEdit::CursorMoved
+            }
+            KeyCode::Right if ctrl || alt => {
+                self.move_word_right();
+                FieldEdit::CursorMoved
+            }
+            KeyCode::Left => {
+                self.move_left();
+                FieldEdit::CursorMoved
+            }
+            KeyCode::Right => {
+                self.move_right();
+                FieldEdit::CursorMoved
+            }
+            KeyCode::Home => {
+                self.move_home();
+                FieldEdit::CursorMoved
+            }
+            KeyCode::End => {
+                self.move_end();
+                FieldEdit::CursorMoved
+            }
+            _ => FieldEdit::Unhandled,
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect, prefix: &str, style: Style) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let prefix_width: usize = prefix.chars().map(char_width).sum();
+        let available = (area.width as usize).saturating_sub(prefix_width).max(1);
+
+        let before_cursor: usize = self.text.chars().take(self.cursor).map(char_width).sum();
+        let scroll = before_cursor.saturating_sub(available - 1);
+
+        let mut visible = String::new();
+        let mut column = 0;
+        for ch in self.text.chars() {
+            let width = char_width(ch);
+            if column >= scroll && column + width <= scroll + available {
+                visible.push(ch);
+            }
+            column += width;
+        }
+
+        let line = format!("{}{}", prefix, visible);
+        f.render_widget(Paragraph::new(line).style(style), area);
+
+        let cursor_x = area.x + (prefix_width + before_cursor - scroll) as u16;
+        f.set_cursor_position(Position::new(cursor_x.min(area.x + area.width - 1), area.y));
+    }
+
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn byte_index(&self, char_idx: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_idx)
+            .map(|(i, _)| i)
+            .unwrap_or(self.text.len())
+    }
+
+    fn delete_range(&mut self, start: usize, end: usize) {
+        let (start_byte, end_byte) = (self.byte_index(start), self.byte_index(end));
+        self.text.replace_range(start_byte..end_byte, "");
+        self.cursor = start;
+    }
+
+    fn word_start_before(&self, from: usize) -> usize {
+        let chars: Vec<char> = self.text.chars().collect();
+        let mut idx = from.min(chars.len());
+        while idx > 0 && !is_word_char(chars[idx - 1]) {
+            idx -= 1;
+        }
+        while idx > 0 && is_word_char(chars[idx - 1]) {
+            idx -= 1;
+        }
+        idx
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+fn char_width(ch: char) -> usize {
+    ch.width().unwrap_or(0)
+}
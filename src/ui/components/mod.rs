@@ -3,7 +3,11 @@ pub mod status_bar;
 pub mod message_bar;
 pub mod line_numbers;
 pub mod dialogs;
+pub mod file_tree;
+pub mod input_field;
 
 pub use editor_view::render_editor;
+pub use file_tree::render_file_tree;
+pub use input_field::{FieldEdit, InputField};
 pub use status_bar::render_status_bar;
 pub use message_bar::render_message_bar;
\ No newline at end of file
@@ -1,5 +1,4 @@
 use crate::state::EditorState;
-use crate::ui::theme::Theme;
 use ratatui::{
     layout::Rect,
     style::Style,
@@ -10,20 +9,48 @@ use ratatui::{
 use unicode_width::UnicodeWidthStr;
 
 pub fn render_status_bar(f: &mut Frame, state: &EditorState, area: Rect) {
-    let theme = Theme::default();
+    let theme = state.theme();
     let document = state.document();
     let cursor = state.cursor();
 
     let modified = if document.is_modified() { " [+]" } else { "" };
+    let read_only = if document.is_read_only() { " [RO]" } else { "" };
     let status = format!(
-        " {} {}  Ln {}, Col {}",
+        " {} {}{}  Ln {}, Col {}",
         document.file_name(),
         modified,
+        read_only,
         cursor.line + 1,
         cursor.column + 1
     );
 
-    let line_count = format!(" {} lines ", document.buffer().len_lines());
+    let settings = document.settings();
+    let format = state
+        .highlighter()
+        .map(|h| h.grammar().name)
+        .into_iter()
+        .chain(settings.charset.map(|c| c.label()))
+        .chain(settings.end_of_line.map(|e| e.label()))
+        .map(|label| format!("[{}] ", label))
+        .collect::<String>();
+    let auto_save = if state.auto_save().is_active() { "[auto-save] " } else { "" };
+    let loading = state
+        .loading()
+        .map(|p| format!("[loading {}%] ", p.percent()))
+        .unwrap_or_default();
+    let pager = match state.pager() {
+        Some(pager) if pager.loading => format!("[loading {}] ", format_bytes(pager.bytes_read)),
+        Some(pager) if pager.follow => "[following] ".to_string(),
+        _ => String::new(),
+    };
+    let line_count = format!(
+        " {}{}{}{}{} lines ",
+        loading,
+        pager,
+        format,
+        auto_save,
+        document.buffer().len_lines()
+    );
 
     let status_width = status.width();
     let line_count_width = line_count.width();
@@ -47,4 +74,19 @@ pub fn render_status_bar(f: &mut Frame, state: &EditorState, area: Rect) {
     );
 
     f.render_widget(paragraph, area);
-}
\ No newline at end of file
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
@@ -5,4 +5,4 @@ pub mod layout;
 
 pub use renderer::render;
 
-pub use theme::Theme;
\ No newline at end of file
+pub use theme::{ColorSupport, Theme, ThemeLoader, ThemePicker};
\ No newline at end of file
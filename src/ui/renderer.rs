@@ -1,6 +1,11 @@
 use crate::state::{EditorMode, EditorState};
-use crate::ui::components::{render_editor, render_message_bar, render_status_bar};
-use crate::ui::components::dialogs::{render_command_palette, render_input_dialog};
+use crate::ui::components::{
+    render_editor, render_file_tree, render_message_bar, render_status_bar,
+};
+use crate::ui::components::dialogs::{
+    render_backup_list, render_command_palette, render_confirmation, render_file_finder,
+    render_input_dialog, render_recent_files, render_recovery_dialog, render_theme_picker,
+};
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::Frame;
 
@@ -14,13 +19,41 @@ pub fn render(f: &mut Frame, state: &EditorState) {
         ])
         .split(f.area());
 
-    render_editor(f, state, chunks[0]);
+    if state.is_explorer_visible() {
+        let width = state.config().ui.explorer_width.min(chunks[0].width / 3);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(width), Constraint::Min(1)])
+            .split(chunks[0]);
+        render_file_tree(f, state, columns[0]);
+        render_editor(f, state, columns[1]);
+    } else {
+        render_editor(f, state, chunks[0]);
+    }
     render_status_bar(f, state, chunks[1]);
     render_message_bar(f, state, chunks[2]);
 
     match state.mode() {
         EditorMode::CommandPalette => render_command_palette(f, state),
-        EditorMode::OpenFile | EditorMode::SaveAs => render_input_dialog(f, state),
+        EditorMode::OpenFile
+        | EditorMode::SaveAs
+        | EditorMode::ExplorerInput
+        | EditorMode::Search
+        | EditorMode::GotoLine => {
+            render_input_dialog(f, state)
+        }
+        EditorMode::Recovery => render_recovery_dialog(f, state),
+        EditorMode::Backups => render_backup_list(f, state),
+        EditorMode::FindFile => render_file_finder(f, state),
+        EditorMode::RecentFiles => render_recent_files(f, state),
+        EditorMode::SelectTheme => render_theme_picker(f, state),
+        EditorMode::Confirm => {
+            if let Some(confirmation) = state.confirmation() {
+                render_confirmation(f, &confirmation.dialog, state.theme());
+            }
+        }
         _ => {}
     }
+
+    state.color_support().apply(f.buffer_mut(), state.theme());
 }
\ No newline at end of file
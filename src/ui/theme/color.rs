@@ -0,0 +1,158 @@
+use super::Theme;
+use crate::config::ColorMode;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use std::env;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    /// No colours; highlights fall back to reverse video.
+    Monochrome,
+}
+
+/// xterm's default RGB values for the 16 ANSI colours.
+const ANSI: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorSupport {
+    pub fn for_mode(mode: ColorMode) -> Self {
+        match mode {
+            ColorMode::Auto => Self::detect(),
+            ColorMode::TrueColor => ColorSupport::TrueColor,
+            ColorMode::Ansi256 => ColorSupport::Ansi256,
+            ColorMode::Ansi16 => ColorSupport::Ansi16,
+            ColorMode::Monochrome => ColorSupport::Monochrome,
+        }
+    }
+
+    pub fn detect() -> Self {
+        if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return ColorSupport::Monochrome;
+        }
+        let colorterm = env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorSupport::TrueColor;
+        }
+        // Windows Terminal does not set TERM.
+        if env::var_os("WT_SESSION").is_some() {
+            return ColorSupport::TrueColor;
+        }
+        let term = env::var("TERM").unwrap_or_default().to_lowercase();
+        match term.as_str() {
+            "dumb" => ColorSupport::Monochrome,
+            t if t.ends_with("-direct") => ColorSupport::TrueColor,
+            t if t.contains("256color") => ColorSupport::Ansi256,
+            _ => ColorSupport::Ansi16,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorSupport::TrueColor => "truecolor",
+            ColorSupport::Ansi256 => "256 colours",
+            ColorSupport::Ansi16 => "16 colours",
+            ColorSupport::Monochrome => "monochrome",
+        }
+    }
+
+    pub fn downsample(&self, color: Color) -> Color {
+        match (self, color) {
+            (ColorSupport::TrueColor, color) => color,
+            (ColorSupport::Monochrome, _) => Color::Reset,
+            (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256((r, g, b))),
+            (ColorSupport::Ansi16, Color::Rgb(r, g, b)) => nearest_16((r, g, b)),
+            (ColorSupport::Ansi16, Color::Indexed(index)) => nearest_16(indexed_rgb(index)),
+            (_, color) => color,
+        }
+    }
+
+    /// Rewrites a drawn frame for the terminal. In monochrome, cells on a
+    /// highlight background (anything but the theme's plain backgrounds)
+    /// are shown in reverse video instead.
+    pub fn apply(&self, buffer: &mut Buffer, theme: &Theme) {
+        if *self == ColorSupport::TrueColor {
+            return;
+        }
+        let plain = [Color::Reset, theme.background, theme.cursor_line, theme.dialog_bg];
+        for cell in buffer.content.iter_mut() {
+            if *self == ColorSupport::Monochrome && !plain.contains(&cell.bg) {
+                cell.modifier.insert(Modifier::REVERSED);
+            }
+            cell.fg = self.downsample(cell.fg);
+            cell.bg = self.downsample(cell.bg);
+            cell.underline_color = self.downsample(cell.underline_color);
+        }
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).unsigned_abs();
+    // Weighted for how sensitive the eye is to each channel.
+    2 * d(a.0, b.0).pow(2) + 4 * d(a.1, b.1).pow(2) + 3 * d(a.2, b.2).pow(2)
+}
+
+/// Picks from the 6x6x6 cube and the grey ramp; the first 16 entries are
+/// left alone because terminals let users redefine them.
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    let level = |v: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| CUBE_LEVELS[i].abs_diff(v))
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (level(rgb.0), level(rgb.1), level(rgb.2));
+    let cube_index = 16 + 36 * r + 6 * g + b;
+    let cube = (CUBE_LEVELS[r], CUBE_LEVELS[g], CUBE_LEVELS[b]);
+
+    let average = (u32::from(rgb.0) + u32::from(rgb.1) + u32::from(rgb.2)) / 3;
+    let step = (average.saturating_sub(3) / 10).min(23) as u8;
+    let grey = 8 + 10 * step;
+
+    if distance(rgb, (grey, grey, grey)) < distance(rgb, cube) {
+        232 + step
+    } else {
+        cube_index as u8
+    }
+}
+
+fn nearest_16(rgb: (u8, u8, u8)) -> Color {
+    ANSI.iter()
+        .min_by_key(|(_, ansi)| distance(rgb, *ansi))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => ANSI[index as usize].1,
+        16..=231 => {
+            let i = index - 16;
+            let level = |v: u8| CUBE_LEVELS[v as usize];
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        _ => {
+            let grey = 8 + 10 * (index - 232);
+            (grey, grey, grey)
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use super::Theme;
+use crate::utils::paths;
+use ratatui::style::{Color, Modifier, Style};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use toml::{Table, Value};
+
+pub const DEFAULT_THEME: &str = "dark";
+const THEMES_DIR: &str = "themes";
+const MAX_INHERIT_DEPTH: usize = 8;
+
+const BUILTIN: &[(&str, &str)] = &[
+    ("dark", include_str!("themes/dark.toml")),
+    ("light", include_str!("themes/light.toml")),
+    ("high-contrast", include_str!("themes/high-contrast.toml")),
+];
+
+const STYLE_KEYS: &[&str] = &["fg", "bg", "bold", "dim", "italic", "underline", "reversed"];
+
+pub struct ThemeLoader;
+
+impl ThemeLoader {
+    pub fn user_dir() -> Option<PathBuf> {
+        paths::config_dir().map(|d| d.join(THEMES_DIR))
+    }
+
+    pub fn names() -> Vec<String> {
+        let mut names: Vec<String> = BUILTIN.iter().map(|(name, _)| name.to_string()).collect();
+        let mut user: Vec<String> = Self::user_dir()
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+            .filter(|name| !names.contains(name))
+            .collect();
+        user.sort();
+        names.extend(user);
+        names
+    }
+
+    /// Loads a theme by name. A user theme shadows the bundled one of the
+    /// same name; `default` is the bundled dark theme.
+    pub fn load(name: &str) -> Result<Theme, String> {
+        Self::load_from(name, true, 0)
+    }
+
+    pub fn builtin(name: &str) -> Result<Theme, String> {
+        Self::load_from(name, false, 0)
+    }
+
+    fn load_from(name: &str, user: bool, depth: usize) -> Result<Theme, String> {
+        let name = canonical(name);
+        if depth > MAX_INHERIT_DEPTH {
+            return Err(format!("theme '{}' inherits too deeply", name));
+        }
+        let user_file = Self::user_dir()
+            .filter(|_| user)
+            .map(|dir| dir.join(format!("{}.toml", name)))
+            .filter(|path| path.is_file());
+        let (label, source) = match user_file {
+            Some(path) => {
+                let label = format!("{}/{}.toml", THEMES_DIR, name);
+                let source = fs::read_to_string(&path).map_err(|e| format!("{}: {}", label, e))?;
+                (label, source)
+            }
+            None => match BUILTIN.iter().find(|(builtin, _)| *builtin == name) {
+                Some((_, source)) => (format!("{} (bundled)", name), source.to_string()),
+                None => return Err(format!("unknown theme '{}'", name)),
+            },
+        };
+
+        let table: Table = source.parse().map_err(|e: toml::de::Error| {
+            match e.span().map(|s| source[..s.start].matches('\n').count() + 1) {
+                Some(line) => format!("{}:{}: {}", label, line, e.message()),
+                None => format!("{}: {}", label, e.message()),
+            }
+        })?;
+        let mut theme = match table.get("inherits") {
+            None => Theme::base(),
+            // A user theme may extend the bundled theme it shadows.
+            Some(Value::String(base)) => {
+                Self::load_from(base, user && canonical(base) != name, depth + 1)?
+            }
+            Some(_) => return Err(format!("{}: inherits must be a theme name", label)),
+        };
+        theme.name = name.to_string();
+        apply(&mut theme, &table).map_err(|e| format!("{}: {}", label, e))?;
+        Ok(theme)
+    }
+}
+
+fn canonical(name: &str) -> &str {
+    match name {
+        "default" => DEFAULT_THEME,
+        _ => name,
+    }
+}
+
+fn apply(theme: &mut Theme, table: &Table) -> Result<(), String> {
+    for (key, value) in table {
+        match (key.as_str(), value) {
+            ("inherits", _) => {}
+            ("ui", Value::Table(ui)) => {
+                for (key, value) in ui {
+                    let slot = color_slot(theme, key)
+                        .ok_or_else(|| format!("unknown ui colour '{}'", key))?;
+                    *slot = parse_color(value).map_err(|e| format!("ui.{}: {}", key, e))?;
+                }
+            }
+            ("syntax", Value::Table(syntax)) => {
+                for (scope, value) in syntax {
+                    apply_scope(theme, scope, value)?;
+                }
+            }
+            (key, _) => return Err(format!("unexpected key '{}'", key)),
+        }
+    }
+    Ok(())
+}
+
+/// A scope is either a colour or a table of style attributes. Other keys in
+/// the table are child scopes, so `keyword.control = "red"` works with or
+/// without quoting the key.
+fn apply_scope(theme: &mut Theme, scope: &str, value: &Value) -> Result<(), String> {
+    let table = match value {
+        Value::Table(table) => table,
+        value => {
+            let color = parse_color(value).map_err(|e| format!("syntax.{}: {}", scope, e))?;
+            theme.syntax.insert(scope.to_string(), Style::default().fg(color));
+            return Ok(());
+        }
+    };
+
+    let mut style = None;
+    for (key, value) in table {
+        if !STYLE_KEYS.contains(&key.as_str()) {
+            apply_scope(theme, &format!("{}.{}", scope, key), value)?;
+            continue;
+        }
+        let field = |e: String| format!("syntax.{}.{}: {}", scope, key, e);
+        let current: &mut Style = style.get_or_insert_with(Style::default);
+        match key.as_str() {
+            "fg" => *current = current.fg(parse_color(value).map_err(field)?),
+            "bg" => *current = current.bg(parse_color(value).map_err(field)?),
+            attribute => {
+                let Value::Boolean(enabled) = value else {
+                    return Err(field("expected true or false".to_string()));
+                };
+                let modifier = match attribute {
+                    "bold" => Modifier::BOLD,
+                    "dim" => Modifier::DIM,
+                    "italic" => Modifier::ITALIC,
+                    "underline" => Modifier::UNDERLINED,
+                    _ => Modifier::REVERSED,
+                };
+                if *enabled {
+                    *current = current.add_modifier(modifier);
+                }
+            }
+        }
+    }
+    if let Some(style) = style {
+        theme.syntax.insert(scope.to_string(), style);
+    }
+    Ok(())
+}
+
+/// Colour names, `#rrggbb` or a 0-255 palette index.
+fn parse_color(value: &Value) -> Result<Color, String> {
+    match value {
+        Value::String(name) => {
+            Color::from_str(name).map_err(|_| format!("invalid colour '{}'", name))
+        }
+        Value::Integer(index) => u8::try_from(*index)
+            .map(Color::Indexed)
+            .map_err(|_| format!("colour index {} is not between 0 and 255", index)),
+        _ => Err("expected a colour name, #rrggbb or a palette index".to_string()),
+    }
+}
+
+fn color_slot<'a>(theme: &'a mut Theme, key: &str) -> Option<&'a mut Color> {
+    Some(match key {
+        "background" => &mut theme.background,
+        "foreground" => &mut theme.foreground,
+        "line_number" => &mut theme.line_number,
+        "line_number_current" => &mut theme.line_number_current,
+        "cursor_line" => &mut theme.cursor_line,
+        "selection" => &mut theme.selection,
+        "search_match" => &mut theme.search_match,
+        "bracket_match" => &mut theme.bracket_match,
+        "line_overflow" => &mut theme.line_overflow,
+        "status_bar_bg" => &mut theme.status_bar_bg,
+        "status_bar_fg" => &mut theme.status_bar_fg,
+        "message_bar" => &mut theme.message_bar,
+        "dialog_bg" => &mut theme.dialog_bg,
+        "dialog_fg" => &mut theme.dialog_fg,
+        "dialog_border" => &mut theme.dialog_border,
+        "dialog_highlight" => &mut theme.dialog_highlight,
+        "diff_added" => &mut theme.diff_added,
+        "diff_removed" => &mut theme.diff_removed,
+        "tree_directory" => &mut theme.tree_directory,
+        "tree_current_file" => &mut theme.tree_current_file,
+        _ => return None,
+    })
+}
@@ -0,0 +1,86 @@
+mod color;
+mod loader;
+mod picker;
+
+pub use color::ColorSupport;
+pub use loader::ThemeLoader;
+pub use picker::ThemePicker;
+
+use ratatui::style::{Color, Style};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub background: Color,
+    pub foreground: Color,
+    pub line_number: Color,
+    pub line_number_current: Color,
+    pub cursor_line: Color,
+    pub selection: Color,
+    pub search_match: Color,
+    pub bracket_match: Color,
+    pub line_overflow: Color,
+    pub status_bar_bg: Color,
+    pub status_bar_fg: Color,
+    pub message_bar: Color,
+    pub dialog_bg: Color,
+    pub dialog_fg: Color,
+    pub dialog_border: Color,
+    pub dialog_highlight: Color,
+    pub diff_added: Color,
+    pub diff_removed: Color,
+    pub tree_directory: Color,
+    pub tree_current_file: Color,
+    pub syntax: HashMap<String, Style>,
+}
+
+impl Theme {
+    pub fn base() -> Self {
+        Self {
+            name: String::new(),
+            background: Color::Reset,
+            foreground: Color::Reset,
+            line_number: Color::DarkGray,
+            line_number_current: Color::Gray,
+            cursor_line: Color::Reset,
+            selection: Color::DarkGray,
+            search_match: Color::Yellow,
+            bracket_match: Color::DarkGray,
+            line_overflow: Color::LightRed,
+            status_bar_bg: Color::DarkGray,
+            status_bar_fg: Color::White,
+            message_bar: Color::Yellow,
+            dialog_bg: Color::Black,
+            dialog_fg: Color::White,
+            dialog_border: Color::White,
+            dialog_highlight: Color::Yellow,
+            diff_added: Color::Green,
+            diff_removed: Color::Red,
+            tree_directory: Color::Blue,
+            tree_current_file: Color::Yellow,
+            syntax: HashMap::new(),
+        }
+    }
+
+    /// The style for a dotted scope, falling back to its parents:
+    /// `keyword.control` uses `keyword` when it has no style of its own.
+    pub fn syntax_style(&self, scope: &str) -> Style {
+        let mut scope = scope;
+        loop {
+            if let Some(style) = self.syntax.get(scope) {
+                return *style;
+            }
+            match scope.rsplit_once('.') {
+                Some((parent, _)) => scope = parent,
+                None => return Style::default(),
+            }
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        ThemeLoader::builtin(loader::DEFAULT_THEME).expect("bundled theme is valid")
+    }
+}
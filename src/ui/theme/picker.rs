@@ -0,0 +1,49 @@
+use super::Theme;
+
+/// The "Select Theme" list. Moving the selection previews a theme; the
+/// original is kept so cancelling can restore it.
+#[derive(Debug, Clone)]
+pub struct ThemePicker {
+    names: Vec<String>,
+    selected: usize,
+    original: Theme,
+}
+
+impl ThemePicker {
+    pub fn new(names: Vec<String>, original: Theme) -> Self {
+        let selected = names.iter().position(|n| *n == original.name).unwrap_or(0);
+        Self {
+            names,
+            selected,
+            original,
+        }
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_name(&self) -> Option<&str> {
+        self.names.get(self.selected).map(String::as_str)
+    }
+
+    pub fn original(&self) -> &Theme {
+        &self.original
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.names.is_empty() {
+            self.selected = (self.selected + 1) % self.names.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.names.is_empty() {
+            self.selected = (self.selected + self.names.len() - 1) % self.names.len();
+        }
+    }
+}
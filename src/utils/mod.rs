@@ -1,3 +1,5 @@
 mod string_ext;
+pub mod paths;
+pub mod text;
 
-pub use string_ext::StringExt;
\ No newline at end of file
+pub use string_ext::StringExt;
@@ -0,0 +1,57 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+pub fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .filter(|h| !h.is_empty())
+        .map(PathBuf::from)
+}
+
+pub fn state_dir() -> Option<PathBuf> {
+    env::var_os("XDG_STATE_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|h| h.join(".local").join("state")))
+        .map(|d| d.join("kappa"))
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|h| h.join(".config")))
+        .map(|d| d.join("kappa"))
+}
+
+pub fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    }
+}
+
+/// Flattens a path into a single file name. `%`, separators and non-ASCII
+/// bytes are written as `%XX`, so different paths never share a name.
+pub fn mangle(path: &Path) -> String {
+    let mut name = String::new();
+    for &byte in absolute(path).as_os_str().as_encoded_bytes() {
+        match byte {
+            b'%' | b'/' | b'\\' | ..0x20 | 0x7f.. => name.push_str(&format!("%{:02X}", byte)),
+            _ => name.push(byte as char),
+        }
+    }
+    name
+}
+
+pub fn expand_tilde(input: &str) -> PathBuf {
+    if input == "~" {
+        return home_dir().unwrap_or_else(|| PathBuf::from(input));
+    }
+    match (input.strip_prefix("~/"), home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(input),
+    }
+}
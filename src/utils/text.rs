@@ -0,0 +1,104 @@
+use std::io::{self, Read};
+
+/// Decodes as much of `pending` as forms complete UTF-8, leaving a trailing
+/// partial sequence for the next chunk. Invalid bytes become U+FFFD.
+pub fn decode_utf8_chunk(pending: &mut Vec<u8>) -> String {
+    let mut text = String::with_capacity(pending.len());
+    let mut rest = &pending[..];
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                rest = &[];
+                break;
+            }
+            Err(e) => {
+                let (valid, after) = rest.split_at(e.valid_up_to());
+                text.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                match e.error_len() {
+                    Some(len) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        rest = &after[len..];
+                    }
+                    None => {
+                        rest = after;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    let consumed = pending.len() - rest.len();
+    pending.drain(..consumed);
+    text
+}
+
+/// Reads `reader` to the end in `chunk`-byte reads, passing each decoded
+/// piece and its size in bytes to `on_text`. Returns `None` once `on_text`
+/// returns false, otherwise the undecodable tail and how reading ended.
+pub fn stream_utf8<R: Read>(
+    mut reader: R,
+    chunk: usize,
+    mut on_text: impl FnMut(String, usize) -> bool,
+) -> Option<(String, io::Result<()>)> {
+    let mut buf = vec![0; chunk];
+    let mut pending = Vec::new();
+    let result = loop {
+        match reader.read(&mut buf) {
+            Ok(0) => break Ok(()),
+            Ok(n) => {
+                pending.extend_from_slice(&buf[..n]);
+                if !on_text(decode_utf8_chunk(&mut pending), n) {
+                    return None;
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => break Err(e),
+        }
+    };
+    Some((String::from_utf8_lossy(&pending).into_owned(), result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_a_split_char_for_the_next_chunk() {
+        let mut pending = "aé".as_bytes()[..2].to_vec();
+        assert_eq!(decode_utf8_chunk(&mut pending), "a");
+        assert_eq!(pending, vec![0xc3]);
+        pending.push(0xa9);
+        assert_eq!(decode_utf8_chunk(&mut pending), "é");
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn invalid_bytes_do_not_swallow_a_split_char() {
+        let mut pending = vec![b'a', 0xff, b'b', 0xe2, 0x82];
+        assert_eq!(decode_utf8_chunk(&mut pending), "a\u{fffd}b");
+        assert_eq!(pending, vec![0xe2, 0x82]);
+        pending.push(0xac);
+        assert_eq!(decode_utf8_chunk(&mut pending), "€");
+    }
+
+    #[test]
+    fn replaces_each_invalid_sequence() {
+        let mut pending = vec![0xff, 0xfe, b'x', 0xc3, b'y'];
+        assert_eq!(decode_utf8_chunk(&mut pending), "\u{fffd}\u{fffd}x\u{fffd}y");
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn streams_across_small_reads() {
+        let input = "héllo wörld €".as_bytes();
+        let mut out = String::new();
+        let (rest, result) = stream_utf8(input, 3, |text, _| {
+            out.push_str(&text);
+            true
+        })
+        .unwrap();
+        assert!(result.is_ok());
+        assert_eq!(out + &rest, "héllo wörld €");
+    }
+}